@@ -0,0 +1,48 @@
+//! compares naive and tiled transpose, run with `cargo bench --bench transpose`.
+//! Software adapters such as llvmpipe emulate workgroup barriers, so tiled kernel only pays off on GPUs
+
+use std::time::{Duration, Instant};
+
+use learning_wgpu::{transpose, transpose_tiled, AdapterOptions, GpuContext};
+
+// runs of every kernel, the first one compiles pipeline and isn't measured
+const RUNS: u32 = 5;
+
+fn main() {
+    let ctx = smol::block_on(GpuContext::new(&AdapterOptions::default()))
+        .expect("benchmark needs GPU adapter");
+    println!("adapter: {}", ctx.info.name);
+
+    for (rows, cols) in [(1024, 1024), (4096, 4096), (1000, 6000)] {
+        let x: Vec<f32> = (0..rows * cols).map(|idx| idx as f32).collect();
+        println!("{rows}×{cols} f32");
+
+        let naive = measure(|| smol::block_on(transpose(&ctx, &x, rows, cols)).unwrap());
+        report("naive", naive, rows * cols);
+        for tile in [16, 32] {
+            let tiled =
+                measure(|| smol::block_on(transpose_tiled(&ctx, &x, rows, cols, tile)).unwrap());
+            report(&format!("tiled {tile}"), tiled, rows * cols);
+        }
+    }
+}
+
+// average time of single run, including upload and download which are the same for both kernels
+fn measure(mut run: impl FnMut() -> Vec<f32>) -> Duration {
+    run();
+    let start = Instant::now();
+    for _ in 0..RUNS {
+        run();
+    }
+    start.elapsed() / RUNS
+}
+
+fn report(kernel: &str, time: Duration, len: usize) {
+    // every element is read and written once
+    let bytes = 2 * len * size_of::<f32>();
+    println!(
+        "  {kernel:>9}: {:>9.2} ms {:>7.2} GB/s",
+        time.as_secs_f64() * 1e3,
+        bytes as f64 / time.as_secs_f64() / 1e9
+    );
+}
@@ -0,0 +1,75 @@
+use wgpu::{Adapter, Backends, Instance, PowerPreference, RequestAdapterOptions, Surface};
+
+use crate::Error;
+
+/// adapter selection
+/// Used by both compute and render paths to pick GPU
+#[derive(Clone, Debug)]
+pub struct AdapterOptions {
+    pub backends: Backends,
+    /// `HighPerformance` by default, so discrete GPU of hybrid machine is preferred
+    pub power_preference: PowerPreference,
+    /// index into `enumerate_adapters`, overrides power preference and fallback
+    pub adapter_index: Option<usize>,
+    /// forces software adapter
+    pub force_fallback_adapter: bool,
+}
+
+impl Default for AdapterOptions {
+    fn default() -> Self {
+        Self {
+            backends: Backends::all(),
+            power_preference: PowerPreference::HighPerformance,
+            adapter_index: None,
+            force_fallback_adapter: false,
+        }
+    }
+}
+
+/// generates WGPU instance limited to selected backends
+pub fn create_instance(options: &AdapterOptions) -> Instance {
+    Instance::new(wgpu::InstanceDescriptor {
+        backends: options.backends,
+        ..Default::default()
+    })
+}
+
+/// lists all adapters of selected backends, in order used by `adapter_index`
+pub fn enumerate_adapters(options: &AdapterOptions) -> Vec<Adapter> {
+    create_instance(options).enumerate_adapters(options.backends)
+}
+
+/// aquires adapter according to `options`,
+/// for render paths adapter has to be able to present to `compatible_surface`
+pub async fn request_adapter(
+    instance: &Instance,
+    options: &AdapterOptions,
+    compatible_surface: Option<&Surface<'_>>,
+) -> Result<Adapter, Error> {
+    let Some(index) = options.adapter_index else {
+        return instance
+            .request_adapter(&RequestAdapterOptions {
+                power_preference: options.power_preference,
+                force_fallback_adapter: options.force_fallback_adapter,
+                compatible_surface,
+            })
+            .await
+            .ok_or(Error::AdapterAquasitionError);
+    };
+
+    let mut adapters = instance.enumerate_adapters(options.backends);
+    if index >= adapters.len() {
+        return Err(Error::InvalidInput(format!(
+            "adapter index {index} out of range, {} adapters available",
+            adapters.len()
+        )));
+    }
+    let adapter = adapters.swap_remove(index);
+
+    match compatible_surface {
+        Some(surface) if !adapter.is_surface_supported(surface) => {
+            Err(Error::AdapterAquasitionError)
+        }
+        _ => Ok(adapter),
+    }
+}
@@ -0,0 +1,254 @@
+use std::{
+    marker::PhantomData,
+    ops::{Bound, Range, RangeBounds},
+    sync::Arc,
+};
+
+use bytemuck::Pod;
+use wgpu::{
+    BindingResource, Buffer, BufferAddress, BufferBinding, BufferSize, BufferUsages, Device, Queue,
+};
+
+use crate::{
+    helpers::{create_storage_buffer, read_buffer, write_buffer},
+    Error, GpuContext,
+};
+
+/// typed GPU buffer
+/// Knows its element count and element type, every buffer can be
+/// uploaded to (`COPY_DST`) and downloaded from (`COPY_SRC`).
+/// Copies operate on whole words, so size of `T` has to be multiple of 4 bytes,
+/// other types fail to compile
+pub struct GpuBuffer<T: Pod> {
+    buffer: Buffer,
+    len: usize,
+    usage: BufferUsages,
+    device: Arc<Device>,
+    queue: Arc<Queue>,
+    _marker: PhantomData<T>,
+}
+
+impl<T: Pod> GpuBuffer<T> {
+    // evaluated when constructor is instantiated, so `GpuBuffer<u8>` is compile error
+    const WORD_SIZED: () = assert!(
+        size_of::<T>() > 0 && size_of::<T>() % 4 == 0,
+        "size of GpuBuffer element has to be multiple of 4 bytes"
+    );
+
+    /// creates buffer initialized with `data`
+    /// fails if buffer exceeds size limits of device
+    pub fn from_slice(
+        ctx: &GpuContext,
+        data: &[T],
+        usage: BufferUsages,
+    ) -> Result<GpuBuffer<T>, Error> {
+        let () = Self::WORD_SIZED;
+        let usage = usage | BufferUsages::COPY_SRC | BufferUsages::COPY_DST;
+        Self::check_size(&ctx.device, data.len(), usage)?;
+        // empty buffers can't be bound, so at least one element is allocated
+        let buffer = if data.is_empty() {
+            Self::allocate(&ctx.device, 0, usage)
+        } else {
+            create_storage_buffer(&ctx.device, data, usage)
+        };
+
+        Ok(GpuBuffer {
+            buffer,
+            len: data.len(),
+            usage,
+            device: ctx.device.clone(),
+            queue: ctx.queue.clone(),
+            _marker: PhantomData,
+        })
+    }
+
+    /// creates buffer of `len` zeroed elements
+    /// fails if buffer exceeds size limits of device
+    pub fn zeroed(
+        ctx: &GpuContext,
+        len: usize,
+        usage: BufferUsages,
+    ) -> Result<GpuBuffer<T>, Error> {
+        let () = Self::WORD_SIZED;
+        let usage = usage | BufferUsages::COPY_SRC | BufferUsages::COPY_DST;
+        Self::check_size(&ctx.device, len, usage)?;
+
+        Ok(GpuBuffer {
+            buffer: Self::allocate(&ctx.device, len, usage),
+            len,
+            usage,
+            device: ctx.device.clone(),
+            queue: ctx.queue.clone(),
+            _marker: PhantomData,
+        })
+    }
+
+    // wgpu panics on buffers and whole buffer bindings above device limits,
+    // so they are rejected before allocation
+    fn check_size(device: &Device, len: usize, usage: BufferUsages) -> Result<(), Error> {
+        let limits = device.limits();
+        let size = len.max(1) as u64 * size_of::<T>() as u64;
+        let max = match usage {
+            usage if usage.contains(BufferUsages::STORAGE) => limits
+                .max_buffer_size
+                .min(limits.max_storage_buffer_binding_size as u64),
+            usage if usage.contains(BufferUsages::UNIFORM) => limits
+                .max_buffer_size
+                .min(limits.max_uniform_buffer_binding_size as u64),
+            _ => limits.max_buffer_size,
+        };
+        if size > max {
+            return Err(Error::InvalidInput(format!(
+                "buffer of {size} bytes exceeds device limit of {max} bytes"
+            )));
+        }
+        Ok(())
+    }
+
+    fn allocate(device: &Device, len: usize, usage: BufferUsages) -> Buffer {
+        // empty buffers can't be bound, so at least one element is allocated
+        let size = len.max(1) * size_of::<T>();
+
+        device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Storage Buffer"),
+            size: size as BufferAddress,
+            usage,
+            mapped_at_creation: false,
+        })
+    }
+
+    /// number of elements
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// size of elements in bytes
+    pub fn size(&self) -> BufferAddress {
+        (self.len * size_of::<T>()) as BufferAddress
+    }
+
+    pub fn usage(&self) -> BufferUsages {
+        self.usage
+    }
+
+    /// underlying untyped buffer
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    pub fn as_binding(&self) -> BindingResource<'_> {
+        self.slice(..).as_binding()
+    }
+
+    /// replaces whole content of buffer with `data`
+    pub fn upload(&self, data: &[T]) -> Result<(), Error> {
+        self.slice(..).upload(data)
+    }
+
+    /// copies whole content of buffer back to host
+    pub async fn download(&self) -> Result<Vec<T>, Error> {
+        self.slice(..).download().await
+    }
+
+    // kernels bind whole buffers as storage, wgpu panics on bindings without that usage
+    pub(crate) fn check_storage(&self) -> Result<(), Error> {
+        if !self.usage.contains(BufferUsages::STORAGE) {
+            return Err(Error::InvalidInput(format!(
+                "buffer with usage {:?} can't be bound as storage",
+                self.usage
+            )));
+        }
+        Ok(())
+    }
+
+    /// view over element `range` of buffer
+    /// panics if range is out of bounds, same as slice indexing
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> GpuBufferSlice<'_, T> {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end + 1,
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => self.len,
+        };
+        assert!(
+            start <= end && end <= self.len,
+            "range {start}..{end} out of bounds for buffer of length {}",
+            self.len
+        );
+
+        GpuBufferSlice {
+            buffer: self,
+            range: start..end,
+        }
+    }
+}
+
+/// element range of `GpuBuffer`
+pub struct GpuBufferSlice<'a, T: Pod> {
+    buffer: &'a GpuBuffer<T>,
+    range: Range<usize>,
+}
+
+impl<'a, T: Pod> GpuBufferSlice<'a, T> {
+    pub fn len(&self) -> usize {
+        self.range.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.range.is_empty()
+    }
+
+    /// offset of slice has to be multiple of `min_storage_buffer_offset_alignment`
+    /// (or `min_uniform_buffer_offset_alignment` for uniforms) to be bound
+    pub fn as_binding(&self) -> BindingResource<'a> {
+        // bindings can't be empty, so empty range binds first element of buffer,
+        // which is always allocated, kernels don't read it
+        let range = match self.range.is_empty() {
+            true => 0..1,
+            false => self.range.clone(),
+        };
+        let offset = (range.start * size_of::<T>()) as BufferAddress;
+        let size = (range.len() * size_of::<T>()) as BufferAddress;
+
+        BindingResource::Buffer(BufferBinding {
+            buffer: &self.buffer.buffer,
+            offset,
+            size: BufferSize::new(size),
+        })
+    }
+
+    /// overwrites elements of slice with `data`
+    pub fn upload(&self, data: &[T]) -> Result<(), Error> {
+        if data.len() != self.len() {
+            return Err(Error::LengthMismatch {
+                expected: self.len(),
+                found: data.len(),
+            });
+        }
+        write_buffer(
+            &self.buffer.queue,
+            &self.buffer.buffer,
+            self.range.start,
+            data,
+        )
+    }
+
+    /// copies elements of slice back to host
+    pub async fn download(&self) -> Result<Vec<T>, Error> {
+        read_buffer(
+            &self.buffer.device,
+            &self.buffer.queue,
+            &self.buffer.buffer,
+            self.range.clone(),
+        )
+        .await
+    }
+}
@@ -0,0 +1,281 @@
+use std::{
+    fmt::Display,
+    fs,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use learning_wgpu::{AdapterOptions, Error, TileConfig};
+use wgpu::{Backends, PowerPreference};
+
+#[derive(Parser)]
+#[command(about = "My take on learning WGPU")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+    #[command(flatten)]
+    pub adapter: AdapterArgs,
+    /// element type of vectors and matrices
+    #[arg(long, global = true, value_enum, default_value_t = ElementType::I32)]
+    pub element: ElementType,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ElementType {
+    F32,
+    U32,
+    I32,
+}
+
+#[derive(Args)]
+pub struct AdapterArgs {
+    /// comma separated list of backends (vulkan, gl, metal, dx12)
+    #[arg(long, global = true)]
+    backend: Option<String>,
+    /// discrete GPU is preferred unless told otherwise
+    #[arg(long, global = true, value_enum, default_value_t = Power::High)]
+    power_preference: Power,
+    /// index of adapter as listed by `adapters`
+    #[arg(long, global = true)]
+    adapter_index: Option<usize>,
+    /// use software fallback adapter
+    #[arg(long, global = true)]
+    fallback: bool,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Power {
+    None,
+    Low,
+    High,
+}
+
+impl AdapterArgs {
+    pub fn options(&self) -> Result<AdapterOptions, Error> {
+        let backends = match &self.backend {
+            Some(list) => {
+                let backends = wgpu::util::parse_backends_from_comma_list(list);
+                if backends.is_empty() {
+                    return Err(Error::InvalidInput(format!("unknown backend `{list}`")));
+                }
+                backends
+            }
+            None => Backends::all(),
+        };
+
+        Ok(AdapterOptions {
+            backends,
+            power_preference: match self.power_preference {
+                Power::None => PowerPreference::None,
+                Power::Low => PowerPreference::LowPower,
+                Power::High => PowerPreference::HighPerformance,
+            },
+            adapter_index: self.adapter_index,
+            force_fallback_adapter: self.fallback,
+        })
+    }
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// lists available adapters with their limits and features
+    Adapters,
+    /// computes `a * x + y`
+    Saxpy {
+        /// scalar multiplier
+        #[arg(long, allow_hyphen_values = true)]
+        a: String,
+        /// file with vector x, `-` reads stdin
+        #[arg(long)]
+        x: PathBuf,
+        /// file with vector y, `-` reads stdin
+        #[arg(long)]
+        y: PathBuf,
+        #[command(flatten)]
+        output: Output,
+    },
+    /// computes dot product of x and y
+    Dot {
+        /// file with vector x, `-` reads stdin
+        #[arg(long)]
+        x: PathBuf,
+        /// file with vector y, `-` reads stdin
+        #[arg(long)]
+        y: PathBuf,
+        #[command(flatten)]
+        output: Output,
+    },
+    /// computes outer product of x and y
+    OuterProduct {
+        /// file with vector x, `-` reads stdin
+        #[arg(long)]
+        x: PathBuf,
+        /// file with vector y, `-` reads stdin
+        #[arg(long)]
+        y: PathBuf,
+        #[command(flatten)]
+        output: Output,
+    },
+    /// transposes R×C matrix
+    Transpose {
+        #[arg(long)]
+        rows: usize,
+        #[arg(long)]
+        cols: usize,
+        /// file with matrix elements in row major order, `-` reads stdin
+        #[arg(long, default_value = "-")]
+        input: PathBuf,
+        #[arg(long, value_enum, default_value_t = TransposeKernel::Naive)]
+        kernel: TransposeKernel,
+        /// edge of block staged in workgroup memory by tiled kernel
+        #[arg(long, default_value_t = 32)]
+        tile: u32,
+        #[command(flatten)]
+        output: Output,
+    },
+    /// multiplies two matrices, one row per line
+    Matmul {
+        /// file with left hand side matrix, `-` reads stdin
+        #[arg(long)]
+        lhs: PathBuf,
+        /// file with right hand side matrix, `-` reads stdin
+        #[arg(long)]
+        rhs: PathBuf,
+        #[arg(long, value_enum, default_value_t = MatmulKernel::Naive)]
+        kernel: MatmulKernel,
+        /// edge of block computed by one workgroup of tiled kernel
+        #[arg(long, default_value_t = TileConfig::default().tile)]
+        tile: u32,
+        /// rows of block computed by one invocation of tiled kernel
+        #[arg(long, default_value_t = TileConfig::default().work_per_thread)]
+        work_per_thread: u32,
+        #[command(flatten)]
+        output: Output,
+    },
+    /// renders triangle
+    Triangle,
+    /// renders rectangle
+    Rectangle,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum MatmulKernel {
+    /// one invocation per element of result
+    Naive,
+    /// blocks of matrices staged in workgroup memory
+    Tiled,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum TransposeKernel {
+    /// one invocation per element of matrix
+    Naive,
+    /// blocks of matrix staged in workgroup memory
+    Tiled,
+}
+
+#[derive(Args)]
+pub struct Output {
+    /// file to write result to, stdout by default
+    #[arg(long)]
+    output: Option<PathBuf>,
+}
+
+impl Output {
+    /// writes `data` as csv with `cols` values per line
+    pub fn write<T: Display>(&self, data: &[T], cols: usize) -> Result<(), Error> {
+        let mut out = String::new();
+        for row in data.chunks(cols.max(1)) {
+            let row: Vec<String> = row.iter().map(ToString::to_string).collect();
+            out += &row.join(",");
+            out += "\n";
+        }
+
+        match &self.output {
+            Some(path) => fs::write(path, out).map_err(|err| input_error(path, err.to_string())),
+            None => Ok(io::stdout().write_all(out.as_bytes())?),
+        }
+    }
+}
+
+/// matrix read from file, rows are lines and values are separated by commas or whitespace
+pub struct Table<T> {
+    pub data: Vec<T>,
+    pub rows: usize,
+    pub cols: usize,
+}
+
+pub fn read_table<T: FromStr>(path: &Path) -> Result<Table<T>, Error> {
+    let content = read_input(path)?;
+
+    let mut table = Table {
+        data: vec![],
+        rows: 0,
+        cols: 0,
+    };
+    for (line_idx, line) in content.lines().enumerate() {
+        let row = parse_values(line)
+            .map_err(|msg| input_error(path, format!("line {}: {msg}", line_idx + 1)))?;
+
+        if row.is_empty() {
+            continue;
+        }
+        if table.rows > 0 && row.len() != table.cols {
+            return Err(input_error(
+                path,
+                format!(
+                    "line {}: expected {} values, found {}",
+                    line_idx + 1,
+                    table.cols,
+                    row.len()
+                ),
+            ));
+        }
+        table.cols = row.len();
+        table.rows += 1;
+        table.data.extend(row);
+    }
+
+    Ok(table)
+}
+
+/// reads vector, values can be split across any number of lines
+pub fn read_vector<T: FromStr>(path: &Path) -> Result<Vec<T>, Error> {
+    parse_values(&read_input(path)?).map_err(|msg| input_error(path, msg))
+}
+
+fn read_input(path: &Path) -> Result<String, Error> {
+    let mut content = String::new();
+    if path == Path::new("-") {
+        io::stdin()
+            .read_to_string(&mut content)
+            .map_err(|err| input_error(path, err.to_string()))?;
+    } else {
+        content = fs::read_to_string(path).map_err(|err| input_error(path, err.to_string()))?;
+    }
+    Ok(content)
+}
+
+/// parses scalar argument `name`
+pub fn parse_scalar<T: FromStr>(name: &str, value: &str) -> Result<T, Error> {
+    value
+        .parse()
+        .map_err(|_| Error::InvalidInput(format!("{name}: `{value}` is not a number")))
+}
+
+fn parse_values<T: FromStr>(text: &str) -> Result<Vec<T>, String> {
+    text.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|value| !value.is_empty())
+        .map(|value| {
+            value
+                .parse::<T>()
+                .map_err(|_| format!("`{value}` is not a number"))
+        })
+        .collect()
+}
+
+fn input_error(path: &Path, msg: String) -> Error {
+    Error::InvalidInput(format!("{}: {msg}", path.display()))
+}
@@ -0,0 +1,76 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use wgpu::{AdapterInfo, ComputePipeline, Device, Queue};
+
+use crate::{
+    adapter::{create_instance, request_adapter, AdapterOptions},
+    helpers::create_pipeline,
+    Error,
+};
+
+/// GPU context
+/// Owns device and queue of aquired GPU and caches compute pipelines,
+/// so kernels called in a loop compile their WGSL only once
+pub struct GpuContext {
+    pub device: Arc<Device>,
+    pub queue: Arc<Queue>,
+    pub info: AdapterInfo,
+    // keyed by shader source and entry point
+    pipelines: Mutex<HashMap<(String, String), Arc<ComputePipeline>>>,
+}
+
+impl GpuContext {
+    /// init device
+    /// Generates WGPU instance and aquires GPU selected by `options`
+    pub async fn new(options: &AdapterOptions) -> Result<GpuContext, Error> {
+        let instance = create_instance(options);
+        let adapter = request_adapter(&instance, options, None).await?;
+
+        // workgroup memory kernels like bitonic sort and kernels over large
+        // buffers use all the adapter offers
+        let adapter_limits = adapter.limits();
+        let limits = wgpu::Limits {
+            max_compute_workgroup_storage_size: adapter_limits.max_compute_workgroup_storage_size,
+            max_storage_buffer_binding_size: adapter_limits.max_storage_buffer_binding_size,
+            max_buffer_size: adapter_limits.max_buffer_size,
+            ..wgpu::Limits::downlevel_defaults()
+        };
+
+        let (device, queue) = adapter
+            .request_device(
+                &wgpu::DeviceDescriptor {
+                    label: None,
+                    required_features: wgpu::Features::empty(),
+                    required_limits: limits,
+                    memory_hints: wgpu::MemoryHints::MemoryUsage,
+                },
+                None,
+            )
+            .await?;
+
+        Ok(GpuContext {
+            device: Arc::new(device),
+            queue: Arc::new(queue),
+            info: adapter.get_info(),
+            pipelines: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// returns compute pipeline for given module and entry point,
+    /// pipeline is created on first use and reused afterwards
+    pub fn pipeline(&self, module: &str, entry_point: &str) -> Arc<ComputePipeline> {
+        // cache stays consistent even if other thread panicked while holding lock
+        let mut pipelines = self
+            .pipelines
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        pipelines
+            .entry((module.to_owned(), entry_point.to_owned()))
+            .or_insert_with(|| Arc::new(create_pipeline(&self.device, module, entry_point)))
+            .clone()
+    }
+}
@@ -0,0 +1,14 @@
+// prepended to shaders that are dispatched with `helpers::dispatch_size`
+
+// linear index of workgroup, dispatch is filled along x first, then y and z
+fn workgroup_index(workgroup_id: vec3<u32>, num_workgroups: vec3<u32>) -> u32 {
+    return (workgroup_id.z * num_workgroups.y + workgroup_id.y) * num_workgroups.x + workgroup_id.x;
+}
+
+// splits linear workgroup index into grid of tiles_x × tiles_y tiles
+// repeated for every matrix of batch, result is (tile x, tile y, batch)
+fn tile_index(index: u32, tiles_x: u32, tiles_y: u32) -> vec3<u32> {
+    let tiles = tiles_x * tiles_y;
+    let tile = index % tiles;
+    return vec3<u32>(tile % tiles_x, tile / tiles_x, index / tiles);
+}
@@ -1 +1 @@
-pub mod dot_product;
\ No newline at end of file
+pub mod dot_product;
@@ -0,0 +1,45 @@
+use std::fmt::Debug;
+
+use bytemuck::Pod;
+
+/// element type of compute kernels
+/// Shaders are written against `T` alias, which is declared for element type
+/// when pipeline is created, so every element type gets its own cached pipeline.
+/// Scalars passed in uniforms are bitcast from `u32`, so elements are 32 bit wide.
+/// `f16` isn't provided, WGSL frontend of naga 22 doesn't implement it yet.
+/// Trait is sealed, only types listed here can be elements
+pub trait Element: Pod + Debug + PartialEq + Send + Sync + sealed::Sealed {
+    /// name of type in WGSL
+    const WGSL_TYPE: &'static str;
+}
+
+mod sealed {
+    // not reachable from outside of crate, so `Element` can't be implemented there
+    pub trait Sealed {}
+
+    impl Sealed for f32 {}
+    impl Sealed for u32 {}
+    impl Sealed for i32 {}
+}
+
+impl Element for f32 {
+    const WGSL_TYPE: &'static str = "f32";
+}
+
+impl Element for u32 {
+    const WGSL_TYPE: &'static str = "u32";
+}
+
+impl Element for i32 {
+    const WGSL_TYPE: &'static str = "i32";
+}
+
+/// prepends declaration of `T` alias to shader `source`
+pub(crate) fn specialise<T: Element>(source: &str) -> String {
+    format!("alias T = {};\n{source}", T::WGSL_TYPE)
+}
+
+/// bit pattern of scalar, shaders read it with `bitcast<T>`
+pub(crate) fn to_bits<T: Element>(value: T) -> u32 {
+    bytemuck::cast(value)
+}
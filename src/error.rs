@@ -1,5 +1,5 @@
-use std::io;
-use wgpu::{CreateSurfaceError, RequestDeviceError};
+use std::{fmt::Display, io};
+use wgpu::{BufferAsyncError, CreateSurfaceError, RequestDeviceError};
 use winit::error::{EventLoopError, OsError};
 
 #[derive(Debug)]
@@ -7,12 +7,64 @@ pub enum Error {
     AdapterAquasitionError,
     DeviceCreationError(RequestDeviceError),
     EventLoopError(EventLoopError),
-    ExecutionError,
     IoError,
     OsError(OsError),
     CreateSurfaceError(CreateSurfaceError),
+    BufferAsyncError(BufferAsyncError),
+    ChannelError(flume::RecvError),
+    LengthMismatch {
+        expected: usize,
+        found: usize,
+    },
+    InvalidInput(String),
+    ShapeMismatch {
+        lhs: (usize, usize),
+        rhs: (usize, usize),
+    },
+    OutputShapeMismatch {
+        expected: (usize, usize),
+        found: (usize, usize),
+    },
+    BatchMismatch {
+        lhs: usize,
+        rhs: usize,
+    },
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::AdapterAquasitionError => write!(f, "no suitable GPU adapter found"),
+            Error::DeviceCreationError(err) => write!(f, "failed to create device: {err}"),
+            Error::EventLoopError(err) => write!(f, "event loop error: {err}"),
+            Error::IoError => write!(f, "io error"),
+            Error::OsError(err) => write!(f, "os error: {err}"),
+            Error::CreateSurfaceError(err) => write!(f, "failed to create surface: {err}"),
+            Error::BufferAsyncError(err) => write!(f, "failed to map buffer: {err}"),
+            Error::ChannelError(err) => write!(f, "buffer mapping was never completed: {err}"),
+            Error::LengthMismatch { expected, found } => {
+                write!(f, "expected {expected} elements, found {found}")
+            }
+            Error::InvalidInput(msg) => write!(f, "invalid input: {msg}"),
+            Error::ShapeMismatch { lhs, rhs } => write!(
+                f,
+                "cannot multiply {}×{} by {}×{} matrix",
+                lhs.0, lhs.1, rhs.0, rhs.1
+            ),
+            Error::OutputShapeMismatch { expected, found } => write!(
+                f,
+                "expected {}×{} output matrix, found {}×{}",
+                expected.0, expected.1, found.0, found.1
+            ),
+            Error::BatchMismatch { lhs, rhs } => {
+                write!(f, "cannot broadcast batch of {lhs} against batch of {rhs}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
 impl From<io::Error> for Error {
     fn from(_: io::Error) -> Self {
         Error::IoError
@@ -42,3 +94,15 @@ impl From<CreateSurfaceError> for Error {
         Error::CreateSurfaceError(value)
     }
 }
+
+impl From<BufferAsyncError> for Error {
+    fn from(value: BufferAsyncError) -> Self {
+        Error::BufferAsyncError(value)
+    }
+}
+
+impl From<flume::RecvError> for Error {
+    fn from(value: flume::RecvError) -> Self {
+        Error::ChannelError(value)
+    }
+}
@@ -1,10 +1,13 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, ops::Range};
 
+use bytemuck::Pod;
 use wgpu::{
-    util::DeviceExt, BindGroup, BindGroupEntry, BindingResource, Buffer, BufferUsages,
-    ComputePipeline, Device,
+    util::DeviceExt, BindGroup, BindGroupEntry, BindingResource, Buffer, BufferAddress,
+    BufferUsages, ComputePipeline, Device, Limits, Queue, COPY_BUFFER_ALIGNMENT,
 };
 
+use crate::Error;
+
 pub fn create_pipeline(device: &Device, module: &str, entry_point: &str) -> ComputePipeline {
     device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
         label: None,
@@ -20,6 +23,24 @@ pub fn create_pipeline(device: &Device, module: &str, entry_point: &str) -> Comp
     })
 }
 
+/// spreads `workgroups` over x, y and z dimension of dispatch
+/// Every dimension stays within `max_compute_workgroups_per_dimension`, so problems of any
+/// size can be dispatched. Shaders recover linear index with `workgroup_index` of `with_dispatch`
+/// and have to skip workgroups past `workgroups`, dispatch may be rounded up
+pub fn dispatch_size(workgroups: u32, limits: &Limits) -> (u32, u32, u32) {
+    let max_workgroups = limits.max_compute_workgroups_per_dimension;
+    let x = workgroups.min(max_workgroups);
+    let rows = workgroups.div_ceil(x.max(1));
+    let y = rows.min(max_workgroups);
+    let z = rows.div_ceil(y.max(1));
+    (x, y, z)
+}
+
+/// prepends `workgroup_index` and `tile_index` functions to shader `source`
+pub(crate) fn with_dispatch(source: &str) -> String {
+    format!("{}\n{source}", include_str!("dispatch.wgsl"))
+}
+
 pub fn create_bind_group<const SIZE: usize>(
     device: &Device,
     compute_pipeline: &ComputePipeline,
@@ -33,11 +54,7 @@ pub fn create_bind_group<const SIZE: usize>(
     })
 }
 
-pub fn create_storage_buffer<T: bytemuck::Pod>(
-    device: &Device,
-    slice: &[T],
-    usage: BufferUsages,
-) -> Buffer {
+pub fn create_storage_buffer<T: Pod>(device: &Device, slice: &[T], usage: BufferUsages) -> Buffer {
     device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
         label: Some("Storage Buffer"),
         contents: bytemuck::cast_slice(slice),
@@ -53,3 +70,79 @@ pub fn create_staging_buffer(device: &Device, size: u64) -> Buffer {
         mapped_at_creation: false,
     })
 }
+
+// copies between buffers and writes through queue work on whole words,
+// wgpu panics on offsets and sizes that aren't multiple of `COPY_BUFFER_ALIGNMENT`
+fn check_alignment(offset: BufferAddress, size: BufferAddress) -> Result<(), Error> {
+    if offset % COPY_BUFFER_ALIGNMENT != 0 || size % COPY_BUFFER_ALIGNMENT != 0 {
+        return Err(Error::InvalidInput(format!(
+            "offset {offset} and size {size} have to be multiple of {COPY_BUFFER_ALIGNMENT} bytes"
+        )));
+    }
+    Ok(())
+}
+
+/// reads `range` elements of `buffer` back to host
+/// buffer needs `COPY_SRC` usage, data goes through a temporary staging buffer.
+/// Byte offset and size of range have to be multiple of 4
+pub async fn read_buffer<T: Pod>(
+    device: &Device,
+    queue: &Queue,
+    buffer: &Buffer,
+    range: Range<usize>,
+) -> Result<Vec<T>, Error> {
+    let offset = (range.start * size_of::<T>()) as BufferAddress;
+    let size = (range.len() * size_of::<T>()) as BufferAddress;
+    if size == 0 {
+        return Ok(vec![]);
+    }
+    check_alignment(offset, size)?;
+
+    // return buffer
+    // MAP_READ allows for reading it
+    // COPY_DST allows for it to be desetination of cpy
+    let staging_buffer = create_staging_buffer(device, size);
+
+    let mut encoder =
+        device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+    encoder.copy_buffer_to_buffer(buffer, offset, &staging_buffer, 0, size);
+    queue.submit(Some(encoder.finish()));
+
+    // sending data back to host
+    let buffer_slice = staging_buffer.slice(..);
+
+    let (sender, receiver) = flume::bounded(1);
+    buffer_slice.map_async(wgpu::MapMode::Read, move |v| {
+        // receiver is only dropped after the value arrives
+        let _ = sender.send(v);
+    });
+
+    // await for GPU processes
+    device.poll(wgpu::Maintain::wait()).panic_on_timeout();
+
+    // await for buffer
+    receiver.recv_async().await??;
+
+    let data = buffer_slice.get_mapped_range();
+    let result: Vec<T> = bytemuck::cast_slice(&data).to_vec();
+    // all veiws have to be dropped manualy
+    drop(data);
+    staging_buffer.unmap();
+
+    Ok(result)
+}
+
+/// writes `data` into `buffer` starting at element `offset`
+/// buffer needs `COPY_DST` usage, write is performed on next submit.
+/// Byte offset and size of data have to be multiple of 4
+pub fn write_buffer<T: Pod>(
+    queue: &Queue,
+    buffer: &Buffer,
+    offset: usize,
+    data: &[T],
+) -> Result<(), Error> {
+    let offset = (offset * size_of::<T>()) as BufferAddress;
+    check_alignment(offset, size_of_val(data) as BufferAddress)?;
+    queue.write_buffer(buffer, offset, bytemuck::cast_slice(data));
+    Ok(())
+}
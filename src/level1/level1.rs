@@ -0,0 +1,236 @@
+use bytemuck::{Pod, Zeroable};
+use wgpu::BufferUsages;
+
+use crate::{
+    buffer::GpuBuffer,
+    dot_product::dot_product::dot_buffers,
+    element::{specialise, to_bits, Element},
+    helpers::{create_bind_group, dispatch_size, with_dispatch},
+    reduction::reduction::{arg_reduce_mapped, reduce_mapped, ArgReduceOp, Map, ReduceOp},
+    saxpy::saxpy::{execute_saxpy, SaxpyParams},
+    Error, GpuContext,
+};
+
+// has to match WORKGROUP_SIZE in shader
+const WORKGROUP_SIZE: u32 = 256;
+
+#[repr(C)]
+#[derive(Clone, Copy, Zeroable, Pod)]
+struct Level1Params {
+    n: u32,
+    // bit patterns of element type
+    a: u32,
+    c: u32,
+    s: u32,
+}
+
+impl Level1Params {
+    fn new(n: usize) -> Level1Params {
+        Level1Params {
+            n: n as u32,
+            a: 0,
+            c: 0,
+            s: 0,
+        }
+    }
+}
+
+/// scales vector
+/// Computes `x = a * x`
+pub fn scal<T: Element>(ctx: &GpuContext, a: T, x: &GpuBuffer<T>) -> Result<(), Error> {
+    x.check_storage()?;
+    let params = Level1Params {
+        a: to_bits(a),
+        ..Level1Params::new(x.len())
+    };
+    execute_elementwise(ctx, "scal", params, x, None)
+}
+
+/// copies vector
+/// Copies `x` into `y` without round trip through host
+pub fn copy<T: Element>(ctx: &GpuContext, x: &GpuBuffer<T>, y: &GpuBuffer<T>) -> Result<(), Error> {
+    check_lengths(x, y)?;
+    // wgpu can't copy buffer onto itself, content stays the same anyway
+    if std::ptr::eq(x, y) {
+        return Ok(());
+    }
+
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+    encoder.copy_buffer_to_buffer(x.buffer(), 0, y.buffer(), 0, x.size());
+    ctx.queue.submit(Some(encoder.finish()));
+    Ok(())
+}
+
+/// swaps vectors
+/// Exchanges content of `x` and `y`
+pub fn swap<T: Element>(ctx: &GpuContext, x: &GpuBuffer<T>, y: &GpuBuffer<T>) -> Result<(), Error> {
+    x.check_storage()?;
+    y.check_storage()?;
+    check_lengths(x, y)?;
+    // buffer can't be bound twice, swapping it with itself changes nothing
+    if std::ptr::eq(x, y) {
+        return Ok(());
+    }
+    execute_elementwise(ctx, "swap", Level1Params::new(x.len()), x, Some(y))
+}
+
+/// plane rotation
+/// Computes `x = c * x + s * y` and `y = c * y - s * x` element wise
+pub fn rot<T: Element>(
+    ctx: &GpuContext,
+    x: &GpuBuffer<T>,
+    y: &GpuBuffer<T>,
+    c: T,
+    s: T,
+) -> Result<(), Error> {
+    x.check_storage()?;
+    y.check_storage()?;
+    check_lengths(x, y)?;
+    let params = Level1Params {
+        c: to_bits(c),
+        s: to_bits(s),
+        ..Level1Params::new(x.len())
+    };
+    execute_elementwise(ctx, "rot", params, x, Some(y))
+}
+
+/// axpy
+/// Computes `y = a * x + y`, same kernel as `saxpy` on vectors already stored on GPU
+pub fn axpy<T: Element>(
+    ctx: &GpuContext,
+    a: T,
+    x: &GpuBuffer<T>,
+    y: &GpuBuffer<T>,
+) -> Result<(), Error> {
+    x.check_storage()?;
+    y.check_storage()?;
+    check_lengths(x, y)?;
+    if !x.is_empty() {
+        execute_saxpy(ctx, SaxpyParams::new(x.len(), a, 1, 1), x, y, None)?;
+    }
+    Ok(())
+}
+
+/// dot product
+/// Computes `sum(x[i] * y[i])`, same kernel as `dot` on vectors already stored on GPU
+pub async fn dot<T: Element>(
+    ctx: &GpuContext,
+    x: &GpuBuffer<T>,
+    y: &GpuBuffer<T>,
+) -> Result<T, Error> {
+    x.check_storage()?;
+    y.check_storage()?;
+    check_lengths(x, y)?;
+    dot_buffers(ctx, x, y).await
+}
+
+/// sum of absolute values
+pub async fn asum<T: Element>(ctx: &GpuContext, x: &GpuBuffer<T>) -> Result<T, Error> {
+    x.check_storage()?;
+    let sum = reduce_mapped(ctx, ReduceOp::Sum, Map::Abs, x, None).await?;
+    Ok(sum.unwrap_or_else(T::zeroed))
+}
+
+/// euclidean norm
+/// Elements are divided by largest absolute value before squaring, as in BLAS,
+/// so squares neither overflow nor underflow
+pub async fn nrm2(ctx: &GpuContext, x: &GpuBuffer<f32>) -> Result<f32, Error> {
+    x.check_storage()?;
+    let Some(scale) = reduce_mapped(ctx, ReduceOp::Max, Map::Abs, x, None).await? else {
+        return Ok(0.0);
+    };
+    // zero vector has nothing to scale, infinities and NaNs propagate
+    if scale == 0.0 || !scale.is_finite() {
+        return Ok(scale);
+    }
+
+    let map = Map::ScaledSquare {
+        scale: to_bits(scale),
+    };
+    let sum = reduce_mapped(ctx, ReduceOp::Sum, map, x, None).await?;
+    Ok(scale * sum.unwrap_or(0.0).sqrt())
+}
+
+/// index of element with largest absolute value
+/// Ties are resolved to the first index, `None` for empty vector
+pub async fn iamax<T: Element>(ctx: &GpuContext, x: &GpuBuffer<T>) -> Result<Option<usize>, Error> {
+    x.check_storage()?;
+    arg_reduce_mapped(ctx, ArgReduceOp::Max, Map::Abs, x).await
+}
+
+// dispatches element wise `entry_point` over `x` and optionally `y`
+fn execute_elementwise<T: Element>(
+    ctx: &GpuContext,
+    entry_point: &str,
+    params: Level1Params,
+    x: &GpuBuffer<T>,
+    y: Option<&GpuBuffer<T>>,
+) -> Result<(), Error> {
+    if x.is_empty() {
+        return Ok(());
+    }
+
+    // buffer that is avaliable for GPU
+    let uniform_buffer_params = GpuBuffer::from_slice(ctx, &[params], BufferUsages::UNIFORM)?;
+
+    // creation of compute pipeline with given entrypoint
+    let compute_pipeline = ctx.pipeline(
+        &specialise::<T>(&with_dispatch(include_str!("shader.wgsl"))),
+        entry_point,
+    );
+
+    // binding buffer to group zero with specific bindings
+    let bind_group = match y {
+        Some(y) => create_bind_group(
+            &ctx.device,
+            &compute_pipeline,
+            [
+                (0, x.as_binding()),
+                (1, y.as_binding()),
+                (2, uniform_buffer_params.as_binding()),
+            ],
+        ),
+        None => create_bind_group(
+            &ctx.device,
+            &compute_pipeline,
+            [(0, x.as_binding()), (2, uniform_buffer_params.as_binding())],
+        ),
+    };
+
+    // workgroups beyond dispatch limit continue in y and z dimension
+    let workgroups = dispatch_size(params.n.div_ceil(WORKGROUP_SIZE), &ctx.device.limits());
+
+    // creates command encoder
+    // its role is to execute pipelines (one ore more)
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+
+    // compute pass is invoked in other scope,
+    // it needs to be dealocated before we can use encoder again
+    {
+        let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: None,
+            timestamp_writes: None,
+        });
+        cpass.set_pipeline(&compute_pipeline);
+        cpass.set_bind_group(0, &bind_group, &[]);
+        cpass.insert_debug_marker(entry_point);
+        cpass.dispatch_workgroups(workgroups.0, workgroups.1, workgroups.2);
+    }
+
+    ctx.queue.submit(Some(encoder.finish()));
+    Ok(())
+}
+
+fn check_lengths<T: Element>(x: &GpuBuffer<T>, y: &GpuBuffer<T>) -> Result<(), Error> {
+    if x.len() != y.len() {
+        return Err(Error::LengthMismatch {
+            expected: x.len(),
+            found: y.len(),
+        });
+    }
+    Ok(())
+}
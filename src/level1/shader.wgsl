@@ -0,0 +1,73 @@
+@group(0)
+@binding(0)
+var<storage, read_write> x: array<T>;
+@group(0)
+@binding(1)
+var<storage, read_write> y: array<T>;
+@group(0)
+@binding(2)
+var<uniform> params: Params;
+
+const WORKGROUP_SIZE: u32 = 256u;
+
+// element handled by invocation
+fn element(local: u32, workgroup_id: vec3<u32>, num_workgroups: vec3<u32>) -> u32 {
+    return workgroup_index(workgroup_id, num_workgroups) * WORKGROUP_SIZE + local;
+}
+
+// x = a * x
+@compute
+@workgroup_size(WORKGROUP_SIZE)
+fn scal(
+    @builtin(local_invocation_index) local: u32,
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    let i = element(local, workgroup_id, num_workgroups);
+    if i < params.n {
+        x[i] = bitcast<T>(params.a) * x[i];
+    }
+}
+
+// exchanges x and y
+@compute
+@workgroup_size(WORKGROUP_SIZE)
+fn swap(
+    @builtin(local_invocation_index) local: u32,
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    let i = element(local, workgroup_id, num_workgroups);
+    if i < params.n {
+        let value = x[i];
+        x[i] = y[i];
+        y[i] = value;
+    }
+}
+
+// plane rotation of points (x, y)
+@compute
+@workgroup_size(WORKGROUP_SIZE)
+fn rot(
+    @builtin(local_invocation_index) local: u32,
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    let i = element(local, workgroup_id, num_workgroups);
+    if i < params.n {
+        let c = bitcast<T>(params.c);
+        let s = bitcast<T>(params.s);
+        let x_value = x[i];
+        let y_value = y[i];
+        x[i] = c * x_value + s * y_value;
+        y[i] = c * y_value - s * x_value;
+    }
+}
+
+struct Params {
+    n: u32,
+    // bit patterns of T
+    a: u32,
+    c: u32,
+    s: u32,
+}
@@ -0,0 +1,41 @@
+#![allow(clippy::module_inception)]
+
+pub mod adapter;
+pub mod buffer;
+pub mod context;
+pub mod dot_product;
+pub mod element;
+pub mod error;
+pub mod helpers;
+pub mod level1;
+pub mod matrix;
+pub mod matrix_dot_product;
+pub mod outer_product;
+pub mod rectangle;
+pub mod reduction;
+pub mod saxpy;
+pub mod scan;
+pub mod sort;
+pub mod transpose;
+pub mod triangle;
+
+pub use adapter::AdapterOptions;
+pub use context::GpuContext;
+pub use dot_product::dot_product::dot;
+pub use element::Element;
+pub use error::Error;
+pub use matrix::Matrix;
+pub use matrix_dot_product::batched::batched_matmul;
+pub use matrix_dot_product::gemm::{gemm, Op};
+pub use matrix_dot_product::gemv::{gemv, gemv_t};
+pub use matrix_dot_product::matrix_dot_product::{matmul, matmul_tiled, TileConfig};
+pub use outer_product::outer_product::outer_product;
+pub use rectangle::rectangle::render_rectangle;
+pub use reduction::reduction::{arg_reduce, reduce, ArgReduceOp, ReduceOp};
+pub use saxpy::saxpy::{axpy, saxpy, saxpy_strided};
+pub use scan::scan::{scan, scan_buffers, ScanMode};
+pub use sort::bitonic_sort::{bitonic_sort, bitonic_sort_rows};
+pub use sort::radix_sort::{radix_sort, radix_sort_f32, radix_sort_pairs, radix_sort_pairs_f32};
+pub use transpose::permute::permute;
+pub use transpose::transpose::{transpose, transpose_batched, transpose_in_place, transpose_tiled};
+pub use triangle::triangle::render_triangle;
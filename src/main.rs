@@ -1,84 +1,146 @@
-use std::io;
+use std::{fmt::Display, process::ExitCode, str::FromStr};
 
-use crate::error::Error;
-use dot_product::dot_product::execute_dot_product;
-use matrix_dot_product::matrix_dot_product::execute_matrix_dot_product;
-use rectangle::rectangle::execute_rectangle;
-use saxpy::saxpy::execute_saxpy;
-use transpose::transpose::execute_transpose;
-use triangle::triangle::execute_triangle;
-use wgpu::{Device, Queue, RequestAdapterOptions};
+use clap::Parser;
+use cli::{
+    parse_scalar, read_table, read_vector, Cli, Command, ElementType, MatmulKernel, TransposeKernel,
+};
+use learning_wgpu::{
+    adapter::enumerate_adapters, dot, matmul, matmul_tiled, outer_product, render_rectangle,
+    render_triangle, saxpy, transpose, transpose_tiled, AdapterOptions, Element, Error, GpuContext,
+    Matrix, TileConfig,
+};
 
-pub mod dot_product;
-pub mod error;
-pub mod helpers;
-pub mod matrix_dot_product;
-pub mod rectangle;
-pub mod saxpy;
-pub mod transpose;
-pub mod triangle;
-fn main() -> Result<(), Error> {
-    // let (device, queue) = smol::block_on(init_device())?;
+mod cli;
 
-    print!(
-        r#"
-Compute shaders:
-    (1) saxpy
-    (2) vec dot product
-    (3) transpose
-    (4) matrix dot product
-Render shaders: 
-    (5) triangle
-    (6) rectangle
-"#
-    );
+fn main() -> ExitCode {
+    let cli = Cli::parse();
 
-    let mut buffer = String::new();
-    io::stdin().read_line(&mut buffer)?;
-    let maybe_u32 = buffer[..buffer.len() - 1].parse::<u32>();
-
-    match maybe_u32 {
-        Ok(a @ 1..=4) => {
-            let (device, queue) = smol::block_on(init_compute_device())?;
-            match a {
-                1 => smol::block_on(execute_saxpy(device, queue))?,
-                2 => smol::block_on(execute_dot_product(device, queue))?,
-                3 => smol::block_on(execute_transpose(device, queue))?,
-                4 => smol::block_on(execute_matrix_dot_product(device, queue))?,
-                _ => println!("unreachable!"),
-            };
+    match smol::block_on(run(cli)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
         }
-        Ok(5) => smol::block_on(execute_triangle())?,
-        Ok(6) => smol::block_on(execute_rectangle())?,
+    }
+}
 
-        _ => panic!("incorrect input"),
-    };
+async fn run(cli: Cli) -> Result<(), Error> {
+    let options = cli.adapter.options()?;
 
-    Ok(())
+    match cli.element {
+        ElementType::F32 => execute::<f32>(cli.command, &options).await,
+        ElementType::U32 => execute::<u32>(cli.command, &options).await,
+        ElementType::I32 => execute::<i32>(cli.command, &options).await,
+    }
 }
 
-/// init device
-/// Generates WGPU instance and aquires GPU
-pub async fn init_compute_device() -> Result<(Device, Queue), Error> {
-    let instance = wgpu::Instance::default();
+// runs command with vectors and matrices of `T`
+async fn execute<T: Element + FromStr + Display>(
+    command: Command,
+    options: &AdapterOptions,
+) -> Result<(), Error> {
+    match command {
+        Command::Adapters => {
+            for (idx, adapter) in enumerate_adapters(options).iter().enumerate() {
+                let info = adapter.get_info();
+                println!(
+                    "[{idx}] {} ({:?}, {:?})",
+                    info.name, info.backend, info.device_type
+                );
+                println!("    vendor: {:#x}, device: {:#x}", info.vendor, info.device);
+                println!("    driver: {} {}", info.driver, info.driver_info);
+                println!("    features: {:?}", adapter.features());
+                let limits = format!("{:#?}", adapter.limits());
+                println!("    limits: {}", limits.replace('\n', "\n    "));
+            }
+            Ok(())
+        }
+        Command::Saxpy { a, x, y, output } => {
+            let a = parse_scalar::<T>("a", &a)?;
+            let (x, y) = (read_vector::<T>(&x)?, read_vector::<T>(&y)?);
+            if x.len() != y.len() {
+                return Err(Error::InvalidInput(format!(
+                    "x has {} elements but y has {}",
+                    x.len(),
+                    y.len()
+                )));
+            }
+
+            let ctx = GpuContext::new(options).await?;
+            let result = saxpy(&ctx, a, &x, &y).await?;
+            output.write(&result, result.len())
+        }
+        Command::Dot { x, y, output } => {
+            let (x, y) = (read_vector::<T>(&x)?, read_vector::<T>(&y)?);
+            if x.len() != y.len() {
+                return Err(Error::InvalidInput(format!(
+                    "x has {} elements but y has {}",
+                    x.len(),
+                    y.len()
+                )));
+            }
+
+            let ctx = GpuContext::new(options).await?;
+            let result = dot(&ctx, &x, &y).await?;
+            output.write(&[result], 1)
+        }
+        Command::OuterProduct { x, y, output } => {
+            let (x, y) = (read_vector::<T>(&x)?, read_vector::<T>(&y)?);
 
-    let adapter = match instance
-        .request_adapter(&RequestAdapterOptions::default())
-        .await
-    {
-        Some(adapter) => adapter,
-        None => return Err(Error::AdapterAquasitionError),
-    };
+            let ctx = GpuContext::new(options).await?;
+            let result = outer_product(&ctx, &x, &y).await?;
+            output.write(&result, y.len())
+        }
+        Command::Transpose {
+            rows,
+            cols,
+            input,
+            kernel,
+            tile,
+            output,
+        } => {
+            let data = read_table::<T>(&input)?.data;
+            if data.len() != rows * cols {
+                return Err(Error::InvalidInput(format!(
+                    "expected {rows}×{cols} = {} elements, found {}",
+                    rows * cols,
+                    data.len()
+                )));
+            }
 
-    Ok(adapter
-        .request_device(
-            &wgpu::DeviceDescriptor {
-                label: None,
-                required_features: wgpu::Features::empty(),
-                required_limits: wgpu::Limits::downlevel_defaults(),
-                memory_hints: wgpu::MemoryHints::MemoryUsage,
-            },
-            None,
-        )
-        .await?)
+            let ctx = GpuContext::new(options).await?;
+            let result = match kernel {
+                TransposeKernel::Naive => transpose(&ctx, &data, rows, cols).await?,
+                TransposeKernel::Tiled => transpose_tiled(&ctx, &data, rows, cols, tile).await?,
+            };
+            output.write(&result, rows)
+        }
+        Command::Matmul {
+            lhs,
+            rhs,
+            kernel,
+            tile,
+            work_per_thread,
+            output,
+        } => {
+            let (lhs, rhs) = (read_table::<T>(&lhs)?, read_table::<T>(&rhs)?);
+
+            let ctx = GpuContext::new(options).await?;
+            let matrix_x = Matrix::new(lhs.data, lhs.rows, lhs.cols)?;
+            let matrix_y = Matrix::new(rhs.data, rhs.rows, rhs.cols)?;
+            let result = match kernel {
+                MatmulKernel::Naive => matmul(&ctx, &matrix_x, &matrix_y).await?,
+                MatmulKernel::Tiled => {
+                    let config = TileConfig {
+                        tile,
+                        work_per_thread,
+                    };
+                    matmul_tiled(&ctx, &matrix_x, &matrix_y, config).await?
+                }
+            };
+            output.write(result.data(), result.cols())
+        }
+        Command::Triangle => render_triangle(options).await,
+        Command::Rectangle => render_rectangle(options).await,
+    }
 }
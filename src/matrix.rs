@@ -0,0 +1,93 @@
+use std::fmt::Display;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::Error;
+
+/// row major matrix of arbitrary size
+#[derive(Clone, Debug, PartialEq)]
+pub struct Matrix<T> {
+    data: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T: Pod> Matrix<T> {
+    /// creates `rows × cols` matrix from row major `data`
+    pub fn new(data: Vec<T>, rows: usize, cols: usize) -> Result<Matrix<T>, Error> {
+        if data.len() != rows * cols {
+            return Err(Error::LengthMismatch {
+                expected: rows * cols,
+                found: data.len(),
+            });
+        }
+        Ok(Matrix { data, rows, cols })
+    }
+
+    pub fn zeros(rows: usize, cols: usize) -> Matrix<T> {
+        Matrix {
+            data: vec![T::zeroed(); rows * cols],
+            rows,
+            cols,
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// elements in row major order
+    pub fn data(&self) -> &[T] {
+        &self.data
+    }
+
+    pub fn into_data(self) -> Vec<T> {
+        self.data
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> T {
+        self.data[row * self.cols + col]
+    }
+}
+
+impl<T: Display> Display for Matrix<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut data = String::new();
+        data += "[\n";
+
+        for row in self.data.chunks(self.cols.max(1)) {
+            for value in row {
+                data += &format!(" {},", value);
+            }
+            data += "\n";
+        }
+        data += "]";
+
+        f.write_str(&data)
+    }
+}
+
+/// shape of matrix product `m × k` by `k × n`, passed to shaders as uniform
+#[repr(C)]
+#[derive(Clone, Copy, Zeroable, Pod)]
+pub(crate) struct MatmulShape {
+    pub m: u32,
+    pub k: u32,
+    pub n: u32,
+    _pad: u32,
+}
+
+impl MatmulShape {
+    pub fn new(m: usize, k: usize, n: usize) -> MatmulShape {
+        MatmulShape {
+            m: m as u32,
+            k: k as u32,
+            n: n as u32,
+            _pad: 0,
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use bytemuck::{Pod, Zeroable};
+use wgpu::BufferUsages;
+
+use crate::{
+    buffer::GpuBuffer,
+    element::{specialise, Element},
+    helpers::{create_bind_group, dispatch_size, with_dispatch},
+    matrix::Matrix,
+    Error, GpuContext,
+};
+
+use super::matrix_dot_product::matmul_shape;
+
+// has to match workgroup_size in shader
+const WORKGROUP_SIZE: u32 = 8;
+
+#[repr(C)]
+#[derive(Clone, Copy, Zeroable, Pod)]
+struct BatchedShape {
+    m: u32,
+    k: u32,
+    n: u32,
+    x_batch_stride: u32,
+    y_batch_stride: u32,
+    batch: u32,
+    _pad: [u32; 2],
+}
+
+/// batched matrix multiplication
+/// Computes `lhs[i] * rhs[i]` for `[B, m, k]` and `[B, k, n]` batches in single dispatch,
+/// batch of one matrix is broadcast against the other operand.
+/// Every matrix of batch has to have the same shape
+pub async fn batched_matmul<T: Element>(
+    ctx: &GpuContext,
+    lhs: &[Matrix<T>],
+    rhs: &[Matrix<T>],
+) -> Result<Vec<Matrix<T>>, Error> {
+    let batch = match (lhs.len(), rhs.len()) {
+        (l, r) if l == r => l,
+        (1, r) => r,
+        (l, 1) => l,
+        (l, r) => return Err(Error::BatchMismatch { lhs: l, rhs: r }),
+    };
+    if batch == 0 {
+        return Ok(Vec::new());
+    }
+
+    let (m, k, n) = batch_shape(lhs, rhs, batch)?;
+    if m * n == 0 {
+        return Ok(vec![Matrix::zeros(m, n); batch]);
+    }
+
+    // operand of single matrix is read from the same offset by every batch
+    let stride = |operand: &[Matrix<T>], size: usize| match operand.len() {
+        1 => 0,
+        _ => size as u32,
+    };
+    let shape = BatchedShape {
+        m: m as u32,
+        k: k as u32,
+        n: n as u32,
+        x_batch_stride: stride(lhs, m * k),
+        y_batch_stride: stride(rhs, k * n),
+        batch: batch as u32,
+        _pad: [0; 2],
+    };
+
+    // batches are stored back to back
+    let data_x: Vec<T> = lhs
+        .iter()
+        .flat_map(|matrix| matrix.data())
+        .copied()
+        .collect();
+    let data_y: Vec<T> = rhs
+        .iter()
+        .flat_map(|matrix| matrix.data())
+        .copied()
+        .collect();
+
+    // buffers that are avaliable for GPU
+    let storage_buffer_x = GpuBuffer::from_slice(ctx, &data_x, BufferUsages::STORAGE)?;
+    let storage_buffer_y = GpuBuffer::from_slice(ctx, &data_y, BufferUsages::STORAGE)?;
+    let storage_buffer_out = GpuBuffer::zeroed(ctx, batch * m * n, BufferUsages::STORAGE)?;
+    let uniform_buffer_shape = GpuBuffer::from_slice(ctx, &[shape], BufferUsages::UNIFORM)?;
+
+    // creation of compute pipeline with entrypoint "main"
+    let compute_pipeline = ctx.pipeline(
+        &specialise::<T>(&with_dispatch(include_str!("batched.wgsl"))),
+        "main",
+    );
+
+    // binding buffer to group zero with specific bindings
+    let bind_group = create_bind_group(
+        &ctx.device,
+        &compute_pipeline,
+        [
+            (0, storage_buffer_x.as_binding()),
+            (1, storage_buffer_y.as_binding()),
+            (2, storage_buffer_out.as_binding()),
+            (3, uniform_buffer_shape.as_binding()),
+        ],
+    );
+
+    // creates command encoder
+    // its role is to execute pipelines (one ore more)
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+
+    // compute pass is invoked in other scope,
+    // it needs to be dealocated before we can use encoder again
+    {
+        let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: None,
+            timestamp_writes: None,
+        });
+        cpass.set_pipeline(&compute_pipeline);
+        cpass.set_bind_group(0, &bind_group, &[]);
+        cpass.insert_debug_marker("batched matrix multiplication");
+        // grid of tiles per matrix of batch, tiles beyond dispatch limit
+        // continue in y and z dimension
+        let tiles = (n as u32).div_ceil(WORKGROUP_SIZE) * (m as u32).div_ceil(WORKGROUP_SIZE);
+        let workgroups = dispatch_size(tiles * batch as u32, &ctx.device.limits());
+        cpass.dispatch_workgroups(workgroups.0, workgroups.1, workgroups.2);
+    }
+
+    ctx.queue.submit(Some(encoder.finish()));
+
+    // copy result back to host and split it into batches
+    storage_buffer_out
+        .download()
+        .await?
+        .chunks(m * n)
+        .map(|data| Matrix::new(data.to_vec(), m, n))
+        .collect()
+}
+
+// validates every pair of batch against the first one, returns `(m, k, n)`
+fn batch_shape<T: Element>(
+    lhs: &[Matrix<T>],
+    rhs: &[Matrix<T>],
+    batch: usize,
+) -> Result<(usize, usize, usize), Error> {
+    let shape = matmul_shape(&lhs[0], &rhs[0])?;
+    for idx in 1..batch {
+        let matrix_x = &lhs[idx.min(lhs.len() - 1)];
+        let matrix_y = &rhs[idx.min(rhs.len() - 1)];
+        if matmul_shape(matrix_x, matrix_y)? != shape {
+            return Err(Error::InvalidInput(format!(
+                "matrix {idx} of batch is {}×{} by {}×{}, expected {}×{} by {}×{}",
+                matrix_x.rows(),
+                matrix_x.cols(),
+                matrix_y.rows(),
+                matrix_y.cols(),
+                shape.0,
+                shape.1,
+                shape.1,
+                shape.2
+            )));
+        }
+    }
+    Ok(shape)
+}
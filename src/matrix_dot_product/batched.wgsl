@@ -0,0 +1,55 @@
+@group(0)
+@binding(0)
+var<storage> x: array<T>;
+@group(0)
+@binding(1)
+var<storage> y: array<T>;
+@group(0)
+@binding(2)
+var<storage, read_write> out: array<T>;
+@group(0)
+@binding(3)
+var<uniform> shape: Shape;
+
+fn dot_product(batch: u32, row: u32, col: u32) {
+    let x_offset = batch * shape.x_batch_stride;
+    let y_offset = batch * shape.y_batch_stride;
+
+    var sum = T(0);
+    for (var i = 0u; i < shape.k; i += 1u) {
+        sum += x[x_offset + row * shape.k + i] * y[y_offset + i * shape.n + col];
+    }
+    out[(batch * shape.m + row) * shape.n + col] = sum;
+}
+
+const WORKGROUP_SIZE: u32 = 8u;
+
+@compute
+@workgroup_size(WORKGROUP_SIZE, WORKGROUP_SIZE)
+fn main(
+    @builtin(local_invocation_id) local_id: vec3<u32>,
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    // grid of tiles of out is repeated for every matrix of batch
+    let tiles_x = (shape.n + WORKGROUP_SIZE - 1u) / WORKGROUP_SIZE;
+    let tiles_y = (shape.m + WORKGROUP_SIZE - 1u) / WORKGROUP_SIZE;
+    let tile = tile_index(workgroup_index(workgroup_id, num_workgroups), tiles_x, tiles_y);
+    let row = tile.y * WORKGROUP_SIZE + local_id.y;
+    let col = tile.x * WORKGROUP_SIZE + local_id.x;
+    // dispatch is rounded up to whole workgroups
+    if tile.z < shape.batch && row < shape.m && col < shape.n {
+        dot_product(tile.z, row, col);
+    }
+}
+
+// every x is m × k, every y is k × n and every out is m × n,
+// batch stride of 0 broadcasts single matrix over whole batch
+struct Shape {
+    m: u32,
+    k: u32,
+    n: u32,
+    x_batch_stride: u32,
+    y_batch_stride: u32,
+    batch: u32,
+}
@@ -0,0 +1,155 @@
+use bytemuck::{Pod, Zeroable};
+use wgpu::BufferUsages;
+
+use crate::{
+    buffer::GpuBuffer,
+    element::{specialise, to_bits, Element},
+    helpers::{create_bind_group, dispatch_size, with_dispatch},
+    matrix::Matrix,
+    Error, GpuContext,
+};
+
+// has to match workgroup_size in shader
+const WORKGROUP_SIZE: u32 = 8;
+
+/// operation applied to gemm operand
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Op {
+    #[default]
+    Identity,
+    Transpose,
+}
+
+impl Op {
+    /// shape of `op(matrix)`
+    pub(crate) fn shape<T: Element>(self, matrix: &Matrix<T>) -> (usize, usize) {
+        match self {
+            Op::Identity => (matrix.rows(), matrix.cols()),
+            Op::Transpose => (matrix.cols(), matrix.rows()),
+        }
+    }
+
+    /// strides of `op(matrix)` rows and columns in row major storage of `matrix`
+    pub(crate) fn strides<T: Element>(self, matrix: &Matrix<T>) -> (u32, u32) {
+        match self {
+            Op::Identity => (matrix.cols() as u32, 1),
+            Op::Transpose => (1, matrix.cols() as u32),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Zeroable, Pod)]
+struct GemmParams {
+    m: u32,
+    k: u32,
+    n: u32,
+    a_row_stride: u32,
+    a_col_stride: u32,
+    b_row_stride: u32,
+    b_col_stride: u32,
+    // bit patterns of element type
+    alpha: u32,
+    beta: u32,
+    _pad: [u32; 3],
+}
+
+/// general matrix multiplication
+/// Computes `c = alpha * op(a) * op(b) + beta * c` following BLAS contract,
+/// transposed operands are read with swapped strides instead of being materialized
+#[allow(clippy::too_many_arguments)]
+pub async fn gemm<T: Element>(
+    ctx: &GpuContext,
+    alpha: T,
+    a: &Matrix<T>,
+    op_a: Op,
+    b: &Matrix<T>,
+    op_b: Op,
+    beta: T,
+    c: &mut Matrix<T>,
+) -> Result<(), Error> {
+    let (m, k) = op_a.shape(a);
+    let (k_b, n) = op_b.shape(b);
+    if k != k_b {
+        return Err(Error::ShapeMismatch {
+            lhs: (m, k),
+            rhs: (k_b, n),
+        });
+    }
+    if (c.rows(), c.cols()) != (m, n) {
+        return Err(Error::OutputShapeMismatch {
+            expected: (m, n),
+            found: (c.rows(), c.cols()),
+        });
+    }
+    if m * n == 0 {
+        return Ok(());
+    }
+
+    let (a_row_stride, a_col_stride) = op_a.strides(a);
+    let (b_row_stride, b_col_stride) = op_b.strides(b);
+    let params = GemmParams {
+        m: m as u32,
+        k: k as u32,
+        n: n as u32,
+        a_row_stride,
+        a_col_stride,
+        b_row_stride,
+        b_col_stride,
+        alpha: to_bits(alpha),
+        beta: to_bits(beta),
+        _pad: [0; 3],
+    };
+
+    // buffers that are avaliable for GPU
+    let storage_buffer_a = GpuBuffer::from_slice(ctx, a.data(), BufferUsages::STORAGE)?;
+    let storage_buffer_b = GpuBuffer::from_slice(ctx, b.data(), BufferUsages::STORAGE)?;
+    let storage_buffer_c = GpuBuffer::from_slice(ctx, c.data(), BufferUsages::STORAGE)?;
+    let uniform_buffer_params = GpuBuffer::from_slice(ctx, &[params], BufferUsages::UNIFORM)?;
+
+    // creation of compute pipeline with entrypoint "main"
+    let compute_pipeline = ctx.pipeline(
+        &specialise::<T>(&with_dispatch(include_str!("gemm.wgsl"))),
+        "main",
+    );
+
+    // binding buffer to group zero with specific bindings
+    let bind_group = create_bind_group(
+        &ctx.device,
+        &compute_pipeline,
+        [
+            (0, storage_buffer_a.as_binding()),
+            (1, storage_buffer_b.as_binding()),
+            (2, storage_buffer_c.as_binding()),
+            (3, uniform_buffer_params.as_binding()),
+        ],
+    );
+
+    // creates command encoder
+    // its role is to execute pipelines (one ore more)
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+
+    // compute pass is invoked in other scope,
+    // it needs to be dealocated before we can use encoder again
+    {
+        let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: None,
+            timestamp_writes: None,
+        });
+        cpass.set_pipeline(&compute_pipeline);
+        cpass.set_bind_group(0, &bind_group, &[]);
+        cpass.insert_debug_marker("gemm");
+        // one workgroup per tile of c, tiles beyond dispatch limit continue in y and z dimension
+        let tiles = (n as u32).div_ceil(WORKGROUP_SIZE) * (m as u32).div_ceil(WORKGROUP_SIZE);
+        let workgroups = dispatch_size(tiles, &ctx.device.limits());
+        cpass.dispatch_workgroups(workgroups.0, workgroups.1, workgroups.2);
+    }
+
+    ctx.queue.submit(Some(encoder.finish()));
+
+    // copy result back to host
+    *c = Matrix::new(storage_buffer_c.download().await?, m, n)?;
+    Ok(())
+}
@@ -0,0 +1,68 @@
+@group(0)
+@binding(0)
+var<storage> a: array<T>;
+@group(0)
+@binding(1)
+var<storage> b: array<T>;
+@group(0)
+@binding(2)
+var<storage, read_write> c: array<T>;
+@group(0)
+@binding(3)
+var<uniform> params: Params;
+
+// op(a)[row][i] * op(b)[i][col], transposition is expressed by strides
+fn dot_product(row: u32, col: u32) -> T {
+    var sum = T(0);
+    for (var i = 0u; i < params.k; i += 1u) {
+        let a_idx = row * params.a_row_stride + i * params.a_col_stride;
+        let b_idx = i * params.b_row_stride + col * params.b_col_stride;
+        sum += a[a_idx] * b[b_idx];
+    }
+    return sum;
+}
+
+const WORKGROUP_SIZE: u32 = 8u;
+
+@compute
+@workgroup_size(WORKGROUP_SIZE, WORKGROUP_SIZE)
+fn main(
+    @builtin(local_invocation_id) local_id: vec3<u32>,
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    let tiles_x = (params.n + WORKGROUP_SIZE - 1u) / WORKGROUP_SIZE;
+    let tiles_y = (params.m + WORKGROUP_SIZE - 1u) / WORKGROUP_SIZE;
+    let tile = tile_index(workgroup_index(workgroup_id, num_workgroups), tiles_x, tiles_y);
+    let row = tile.y * WORKGROUP_SIZE + local_id.y;
+    let col = tile.x * WORKGROUP_SIZE + local_id.x;
+    // dispatch is rounded up to whole workgroups
+    if tile.z > 0u || row >= params.m || col >= params.n {
+        return;
+    }
+
+    let idx = row * params.n + col;
+    let alpha = bitcast<T>(params.alpha);
+    let beta = bitcast<T>(params.beta);
+    let product = alpha * dot_product(row, col);
+    // per BLAS, c is not read when beta is zero
+    if beta == T(0) {
+        c[idx] = product;
+    } else {
+        c[idx] = product + beta * c[idx];
+    }
+}
+
+// op(a) is m × k, op(b) is k × n and c is m × n
+struct Params {
+    m: u32,
+    k: u32,
+    n: u32,
+    a_row_stride: u32,
+    a_col_stride: u32,
+    b_row_stride: u32,
+    b_col_stride: u32,
+    // bit patterns of T
+    alpha: u32,
+    beta: u32,
+}
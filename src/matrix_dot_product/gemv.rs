@@ -0,0 +1,142 @@
+use bytemuck::{Pod, Zeroable};
+use wgpu::BufferUsages;
+
+use crate::{
+    buffer::GpuBuffer,
+    element::{specialise, to_bits, Element},
+    helpers::{create_bind_group, dispatch_size, with_dispatch},
+    matrix::Matrix,
+    Error, GpuContext,
+};
+
+use super::gemm::Op;
+
+#[repr(C)]
+#[derive(Clone, Copy, Zeroable, Pod)]
+struct GemvParams {
+    rows: u32,
+    cols: u32,
+    row_stride: u32,
+    col_stride: u32,
+    // bit patterns of element type
+    alpha: u32,
+    beta: u32,
+    _pad: [u32; 2],
+}
+
+/// matrix-vector product
+/// Computes `y = alpha * a * x + beta * y`, every row of `a` is reduced by one workgroup
+pub async fn gemv<T: Element>(
+    ctx: &GpuContext,
+    alpha: T,
+    a: &Matrix<T>,
+    x: &[T],
+    beta: T,
+    y: &mut [T],
+) -> Result<(), Error> {
+    execute_gemv(ctx, alpha, a, Op::Identity, x, beta, y).await
+}
+
+/// transposed matrix-vector product
+/// Computes `y = alpha * a^T * x + beta * y` without materializing `a^T`
+pub async fn gemv_t<T: Element>(
+    ctx: &GpuContext,
+    alpha: T,
+    a: &Matrix<T>,
+    x: &[T],
+    beta: T,
+    y: &mut [T],
+) -> Result<(), Error> {
+    execute_gemv(ctx, alpha, a, Op::Transpose, x, beta, y).await
+}
+
+// executes gemv pipeline for `op(a)`
+async fn execute_gemv<T: Element>(
+    ctx: &GpuContext,
+    alpha: T,
+    a: &Matrix<T>,
+    op_a: Op,
+    x: &[T],
+    beta: T,
+    y: &mut [T],
+) -> Result<(), Error> {
+    let (rows, cols) = op_a.shape(a);
+    if x.len() != cols {
+        return Err(Error::LengthMismatch {
+            expected: cols,
+            found: x.len(),
+        });
+    }
+    if y.len() != rows {
+        return Err(Error::LengthMismatch {
+            expected: rows,
+            found: y.len(),
+        });
+    }
+    if rows == 0 {
+        return Ok(());
+    }
+
+    let (row_stride, col_stride) = op_a.strides(a);
+    let params = GemvParams {
+        rows: rows as u32,
+        cols: cols as u32,
+        row_stride,
+        col_stride,
+        alpha: to_bits(alpha),
+        beta: to_bits(beta),
+        _pad: [0; 2],
+    };
+
+    // buffers that are avaliable for GPU
+    let storage_buffer_a = GpuBuffer::from_slice(ctx, a.data(), BufferUsages::STORAGE)?;
+    let storage_buffer_x = GpuBuffer::from_slice(ctx, x, BufferUsages::STORAGE)?;
+    let storage_buffer_y = GpuBuffer::from_slice(ctx, y, BufferUsages::STORAGE)?;
+    let uniform_buffer_params = GpuBuffer::from_slice(ctx, &[params], BufferUsages::UNIFORM)?;
+
+    // creation of compute pipeline with entrypoint "main"
+    let compute_pipeline = ctx.pipeline(
+        &specialise::<T>(&with_dispatch(include_str!("gemv.wgsl"))),
+        "main",
+    );
+
+    // binding buffer to group zero with specific bindings
+    let bind_group = create_bind_group(
+        &ctx.device,
+        &compute_pipeline,
+        [
+            (0, storage_buffer_a.as_binding()),
+            (1, storage_buffer_x.as_binding()),
+            (2, storage_buffer_y.as_binding()),
+            (3, uniform_buffer_params.as_binding()),
+        ],
+    );
+
+    // one workgroup per row, rows beyond dispatch limit continue in y and z dimension
+    let workgroups = dispatch_size(rows as u32, &ctx.device.limits());
+
+    // creates command encoder
+    // its role is to execute pipelines (one ore more)
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+
+    // compute pass is invoked in other scope,
+    // it needs to be dealocated before we can use encoder again
+    {
+        let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: None,
+            timestamp_writes: None,
+        });
+        cpass.set_pipeline(&compute_pipeline);
+        cpass.set_bind_group(0, &bind_group, &[]);
+        cpass.insert_debug_marker("gemv");
+        cpass.dispatch_workgroups(workgroups.0, workgroups.1, workgroups.2);
+    }
+
+    ctx.queue.submit(Some(encoder.finish()));
+
+    // copy result back to host
+    y.copy_from_slice(&storage_buffer_y.download().await?);
+    Ok(())
+}
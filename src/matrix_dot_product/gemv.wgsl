@@ -0,0 +1,68 @@
+@group(0)
+@binding(0)
+var<storage> a: array<T>;
+@group(0)
+@binding(1)
+var<storage> x: array<T>;
+@group(0)
+@binding(2)
+var<storage, read_write> y: array<T>;
+@group(0)
+@binding(3)
+var<uniform> params: Params;
+
+const WORKGROUP_SIZE: u32 = 64u;
+
+var<workgroup> partial: array<T, WORKGROUP_SIZE>;
+
+// one workgroup reduces one row of op(a)
+@compute
+@workgroup_size(WORKGROUP_SIZE)
+fn main(
+    @builtin(local_invocation_index) local: u32,
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    let row = workgroup_index(workgroup_id, num_workgroups);
+    // whole workgroup leaves together, so barriers below stay uniform
+    if row >= params.rows {
+        return;
+    }
+
+    // op(a)[row][i] * x[i], transposition is expressed by strides
+    var sum = T(0);
+    for (var i = local; i < params.cols; i += WORKGROUP_SIZE) {
+        sum += a[row * params.row_stride + i * params.col_stride] * x[i];
+    }
+    partial[local] = sum;
+
+    for (var stride = WORKGROUP_SIZE / 2u; stride > 0u; stride /= 2u) {
+        workgroupBarrier();
+        if local < stride {
+            partial[local] += partial[local + stride];
+        }
+    }
+
+    if local == 0u {
+        let alpha = bitcast<T>(params.alpha);
+        let beta = bitcast<T>(params.beta);
+        let product = alpha * partial[0];
+        // per BLAS, y is not read when beta is zero
+        if beta == T(0) {
+            y[row] = product;
+        } else {
+            y[row] = product + beta * y[row];
+        }
+    }
+}
+
+// op(a) is rows × cols, x has cols and y has rows elements
+struct Params {
+    rows: u32,
+    cols: u32,
+    row_stride: u32,
+    col_stride: u32,
+    // bit patterns of T
+    alpha: u32,
+    beta: u32,
+}
@@ -1 +1,4 @@
+pub mod batched;
+pub mod gemm;
+pub mod gemv;
 pub mod matrix_dot_product;
\ No newline at end of file
@@ -1,40 +1,48 @@
 @group(0)
 @binding(0)
-var<uniform> x: Matrix; 
+var<storage> x: array<T>;
 @group(0)
 @binding(1)
-var<uniform> y: Matrix;
+var<storage> y: array<T>;
 @group(0)
 @binding(2)
-var<storage, read_write> out: array<i32>;
-
-fn dot_product(x_cord: u32, y_cord: u32) {
-    var x_y = x.size_y;
-    var y_x = x.size_x;
-
-    var out_cord = x_cord * x_y + y_cord;
-
-
-    var sum = 0;
-    for (var i = u32(0); i < x_y; i += u32(1)) {
-      var x_idx = x_cord * x_y + i;
-      var y_idx = y_x * i + y_cord;
+var<storage, read_write> out: array<T>;
+@group(0)
+@binding(3)
+var<uniform> shape: Shape;
 
-      var x_data = x.data[x_idx/4][x_idx % 4];
-      var y_data = y.data[y_idx/4][y_idx % 4];
-        sum += x_data * y_data;
+fn dot_product(row: u32, col: u32) {
+    var sum = T(0);
+    for (var i = 0u; i < shape.k; i += 1u) {
+        sum += x[row * shape.k + i] * y[i * shape.n + col];
     }
-    out[out_cord] = sum;
+    out[row * shape.n + col] = sum;
 }
 
+const WORKGROUP_SIZE: u32 = 8u;
+
 @compute
-@workgroup_size(1)
-fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
-    dot_product(global_id.x, global_id.y);
+@workgroup_size(WORKGROUP_SIZE, WORKGROUP_SIZE)
+fn main(
+    @builtin(local_invocation_id) local_id: vec3<u32>,
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    let tiles_x = (shape.n + WORKGROUP_SIZE - 1u) / WORKGROUP_SIZE;
+    let tiles_y = (shape.m + WORKGROUP_SIZE - 1u) / WORKGROUP_SIZE;
+    let tile = tile_index(workgroup_index(workgroup_id, num_workgroups), tiles_x, tiles_y);
+    let row = tile.y * WORKGROUP_SIZE + local_id.y;
+    let col = tile.x * WORKGROUP_SIZE + local_id.x;
+    // dispatch is rounded up to whole workgroups
+    if tile.z == 0u && row < shape.m && col < shape.n {
+        dot_product(row, col);
+    }
 }
 
-struct Matrix {
-    data: array<vec4<i32>, 64>, 
-    size_x: u32,
-    size_y: u32,
+// x is m × k, y is k × n and out is m × n
+struct Shape {
+    m: u32,
+    k: u32,
+    n: u32,
+    _pad: u32,
 }
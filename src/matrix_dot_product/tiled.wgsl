@@ -0,0 +1,87 @@
+// T alias, TILE and WPT constants are prepended when pipeline is created,
+// each workgroup computes TILE × TILE block of out and each
+// invocation WPT rows of that block
+@group(0)
+@binding(0)
+var<storage> x: array<T>;
+@group(0)
+@binding(1)
+var<storage> y: array<T>;
+@group(0)
+@binding(2)
+var<storage, read_write> out: array<T>;
+@group(0)
+@binding(3)
+var<uniform> shape: Shape;
+
+// rows of block handled by one step of invocations
+const ROWS: u32 = TILE / WPT;
+
+var<workgroup> tile_x: array<array<T, TILE>, TILE>;
+var<workgroup> tile_y: array<array<T, TILE>, TILE>;
+
+@compute
+@workgroup_size(TILE, ROWS)
+fn main(
+    @builtin(local_invocation_id) local_id: vec3<u32>,
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    let tiles_x = (shape.n + TILE - 1u) / TILE;
+    let tiles_y = (shape.m + TILE - 1u) / TILE;
+    let block = tile_index(workgroup_index(workgroup_id, num_workgroups), tiles_x, tiles_y);
+    // whole workgroup of rounded up dispatch leaves together, so barriers below stay uniform
+    if block.z > 0u {
+        return;
+    }
+    let col = block.x * TILE + local_id.x;
+    let block_row = block.y * TILE;
+
+    var acc: array<T, WPT>;
+    let tiles = (shape.k + TILE - 1u) / TILE;
+    for (var t = 0u; t < tiles; t += 1u) {
+        // cooperative load of both tiles, elements outside of matrices are zero
+        for (var w = 0u; w < WPT; w += 1u) {
+            let r = local_id.y + w * ROWS;
+
+            let x_row = block_row + r;
+            let x_col = t * TILE + local_id.x;
+            if x_row < shape.m && x_col < shape.k {
+                tile_x[r][local_id.x] = x[x_row * shape.k + x_col];
+            } else {
+                tile_x[r][local_id.x] = T(0);
+            }
+
+            let y_row = t * TILE + r;
+            if y_row < shape.k && col < shape.n {
+                tile_y[r][local_id.x] = y[y_row * shape.n + col];
+            } else {
+                tile_y[r][local_id.x] = T(0);
+            }
+        }
+        workgroupBarrier();
+
+        for (var i = 0u; i < TILE; i += 1u) {
+            let y_value = tile_y[i][local_id.x];
+            for (var w = 0u; w < WPT; w += 1u) {
+                acc[w] += tile_x[local_id.y + w * ROWS][i] * y_value;
+            }
+        }
+        workgroupBarrier();
+    }
+
+    for (var w = 0u; w < WPT; w += 1u) {
+        let row = block_row + local_id.y + w * ROWS;
+        if row < shape.m && col < shape.n {
+            out[row * shape.n + col] = acc[w];
+        }
+    }
+}
+
+// x is m × k, y is k × n and out is m × n
+struct Shape {
+    m: u32,
+    k: u32,
+    n: u32,
+    _pad: u32,
+}
@@ -0,0 +1,77 @@
+use wgpu::BufferUsages;
+
+use crate::{
+    buffer::GpuBuffer,
+    element::{specialise, Element},
+    helpers::{create_bind_group, dispatch_size, with_dispatch},
+    Error, GpuContext,
+};
+
+// has to match WORKGROUP_SIZE in shader
+const WORKGROUP_SIZE: u32 = 64;
+
+/// outer product
+/// Computes `x.len() × y.len()` matrix of `x[i] * y[j]` in row major order
+pub async fn outer_product<T: Element>(
+    ctx: &GpuContext,
+    x: &[T],
+    y: &[T],
+) -> Result<Vec<T>, Error> {
+    let len = x.len() * y.len();
+    if len == 0 {
+        return Ok(Vec::new());
+    }
+
+    // output buffer that is avaliable for GPU
+    let storage_buffer_out = GpuBuffer::zeroed(ctx, len, BufferUsages::STORAGE)?;
+
+    // buffer that is avaliable for GPU
+    let storage_buffer_x = GpuBuffer::from_slice(ctx, x, BufferUsages::STORAGE)?;
+
+    // buffer that is avaliable for GPU
+    let storage_buffer_y = GpuBuffer::from_slice(ctx, y, BufferUsages::STORAGE)?;
+
+    // creation of compute pipeline with entrypoint "main"
+    let compute_pipeline = ctx.pipeline(
+        &specialise::<T>(&with_dispatch(include_str!("shader.wgsl"))),
+        "main",
+    );
+
+    // binding buffer to group zero with specific bindings
+    let bind_group = create_bind_group(
+        &ctx.device,
+        &compute_pipeline,
+        [
+            (0, storage_buffer_x.as_binding()),
+            (1, storage_buffer_y.as_binding()),
+            (2, storage_buffer_out.as_binding()),
+        ],
+    );
+
+    // creates command encoder
+    // its role is to execute pipelines (one ore more)
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+
+    // compute pass is invoked in other scope,
+    // it needs to be dealocated before we can use encoder again
+    {
+        let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: None,
+            timestamp_writes: None,
+        });
+        cpass.set_pipeline(&compute_pipeline);
+        cpass.set_bind_group(0, &bind_group, &[]);
+        cpass.insert_debug_marker("outer product");
+        // one invocation per element of result, workgroups beyond dispatch limit
+        // continue in y and z dimension
+        let workgroups = dispatch_size((len as u32).div_ceil(WORKGROUP_SIZE), &ctx.device.limits());
+        cpass.dispatch_workgroups(workgroups.0, workgroups.1, workgroups.2);
+    }
+
+    ctx.queue.submit(Some(encoder.finish()));
+
+    // copy result back to host
+    storage_buffer_out.download().await
+}
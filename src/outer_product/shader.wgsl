@@ -0,0 +1,32 @@
+@group(0)
+@binding(0)
+var<storage> x: array<T>; 
+@group(0)
+@binding(1)
+var<storage> y: array<T>;
+@group(0)
+@binding(2)
+var<storage, read_write> out: array<T>;
+
+const WORKGROUP_SIZE: u32 = 64u;
+
+fn outer_product(out_cord: u32) {
+    var y_size = arrayLength(&y);
+    var x_cord = out_cord / y_size;
+    var y_cord = out_cord % y_size;
+    out[out_cord] = x[x_cord]*y[y_cord];
+}
+
+@compute
+@workgroup_size(WORKGROUP_SIZE)
+fn main(
+    @builtin(local_invocation_index) local: u32,
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    // one invocation per element of out, dispatch is rounded up to whole workgroups
+    let i = workgroup_index(workgroup_id, num_workgroups) * WORKGROUP_SIZE + local;
+    if i < arrayLength(&out) {
+        outer_product(i);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,105 @@
+// T alias and functions below are prepended when pipeline is created:
+// map(i) reads i-th element of first pass and better(a, b) tells if a wins over b
+@group(0)
+@binding(0)
+var<storage> x: array<T>;
+@group(0)
+@binding(1)
+var<storage> y: array<T>;
+@group(0)
+@binding(2)
+var<uniform> params: Params;
+@group(0)
+@binding(3)
+var<storage, read_write> out: array<T>;
+@group(0)
+@binding(4)
+var<storage, read_write> out_indices: array<u32>;
+@group(0)
+@binding(5)
+var<storage> indices: array<u32>;
+
+const WORKGROUP_SIZE: u32 = 256u;
+
+var<workgroup> partial: array<T, WORKGROUP_SIZE>;
+var<workgroup> partial_indices: array<u32, WORKGROUP_SIZE>;
+
+// candidate (value, index) wins if it is better or equal with lower index,
+// so result is first extreme element
+fn wins(value: T, index: u32, best: T, best_index: u32) -> bool {
+    return better(value, best) || (value == best && index < best_index);
+}
+
+// tree reduction of extremes, result is written to out[workgroup] and out_indices[workgroup]
+fn arg_reduce(local: u32, workgroup: u32) {
+    for (var stride = WORKGROUP_SIZE / 2u; stride > 0u; stride /= 2u) {
+        workgroupBarrier();
+        if local < stride {
+            let other = local + stride;
+            if wins(partial[other], partial_indices[other], partial[local], partial_indices[local]) {
+                partial[local] = partial[other];
+                partial_indices[local] = partial_indices[other];
+            }
+        }
+    }
+
+    if local == 0u {
+        out[workgroup] = partial[0];
+        out_indices[workgroup] = partial_indices[0];
+    }
+}
+
+// first pass of arg reduction, invocations without elements hold first element,
+// which can't win over real extreme
+@compute
+@workgroup_size(WORKGROUP_SIZE)
+fn arg_reduce_first(
+    @builtin(local_invocation_index) local: u32,
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    var best = map(0u);
+    var best_index = 0u;
+    let stride = num_workgroups.x * WORKGROUP_SIZE;
+    for (var i = workgroup_id.x * WORKGROUP_SIZE + local; i < params.n; i += stride) {
+        let value = map(i);
+        if wins(value, i, best, best_index) {
+            best = value;
+            best_index = i;
+        }
+    }
+    partial[local] = best;
+    partial_indices[local] = best_index;
+
+    arg_reduce(local, workgroup_id.x);
+}
+
+// following passes of arg reduction, partial extremes are in x and their indices in indices
+@compute
+@workgroup_size(WORKGROUP_SIZE)
+fn arg_reduce_partials(
+    @builtin(local_invocation_index) local: u32,
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    var best = x[0];
+    var best_index = indices[0];
+    let stride = num_workgroups.x * WORKGROUP_SIZE;
+    for (var i = workgroup_id.x * WORKGROUP_SIZE + local; i < params.n; i += stride) {
+        if wins(x[i], indices[i], best, best_index) {
+            best = x[i];
+            best_index = indices[i];
+        }
+    }
+    partial[local] = best;
+    partial_indices[local] = best_index;
+
+    arg_reduce(local, workgroup_id.x);
+}
+
+struct Params {
+    // number of elements, or partial results in following passes
+    n: u32,
+    // bit pattern of divisor of `Map::ScaledSquare`
+    scale: u32,
+}
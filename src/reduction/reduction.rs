@@ -0,0 +1,321 @@
+use bytemuck::{Pod, Zeroable};
+use wgpu::{BindGroup, BufferUsages, CommandEncoder, ComputePipeline};
+
+use crate::{
+    buffer::GpuBuffer,
+    element::{specialise, Element},
+    helpers::create_bind_group,
+    Error, GpuContext,
+};
+
+// has to match WORKGROUP_SIZE in shader
+const WORKGROUP_SIZE: usize = 256;
+
+#[repr(C)]
+#[derive(Clone, Copy, Zeroable, Pod)]
+struct ReduceParams {
+    n: u32,
+    scale: u32,
+    _pad: [u32; 2],
+}
+
+/// reduction operator
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReduceOp {
+    Sum,
+    Product,
+    Min,
+    Max,
+}
+
+impl ReduceOp {
+    // WGSL of `combine` and `identity`, min and max are idempotent,
+    // so any element of input is their neutral element
+    fn source(self) -> &'static str {
+        match self {
+            ReduceOp::Sum => {
+                "fn combine(a: T, b: T) -> T { return a + b; }\n\
+                 fn identity(first: T) -> T { return T(0); }\n"
+            }
+            ReduceOp::Product => {
+                "fn combine(a: T, b: T) -> T { return a * b; }\n\
+                 fn identity(first: T) -> T { return T(1); }\n"
+            }
+            ReduceOp::Min => {
+                "fn combine(a: T, b: T) -> T { return min(a, b); }\n\
+                 fn identity(first: T) -> T { return first; }\n"
+            }
+            ReduceOp::Max => {
+                "fn combine(a: T, b: T) -> T { return max(a, b); }\n\
+                 fn identity(first: T) -> T { return first; }\n"
+            }
+        }
+    }
+}
+
+/// arg reduction operator
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArgReduceOp {
+    Min,
+    Max,
+}
+
+impl ArgReduceOp {
+    // WGSL of `better`
+    fn source(self) -> &'static str {
+        match self {
+            ArgReduceOp::Min => "fn better(a: T, b: T) -> bool { return a < b; }\n",
+            ArgReduceOp::Max => "fn better(a: T, b: T) -> bool { return a > b; }\n",
+        }
+    }
+}
+
+/// element wise transformation applied by first pass of reduction
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Map {
+    Identity,
+    /// `x[i] * y[i]`
+    Product,
+    Abs,
+    /// `(x[i] / scale)²`, scale is bit pattern of `T`
+    ScaledSquare {
+        scale: u32,
+    },
+}
+
+impl Map {
+    // WGSL of `map`
+    fn source(self) -> &'static str {
+        match self {
+            Map::Identity => "fn map(i: u32) -> T { return x[i]; }\n",
+            Map::Product => "fn map(i: u32) -> T { return x[i] * y[i]; }\n",
+            Map::Abs => "fn map(i: u32) -> T { return abs(x[i]); }\n",
+            Map::ScaledSquare { .. } => {
+                "fn map(i: u32) -> T { let s = x[i] / bitcast<T>(params.scale); return s * s; }\n"
+            }
+        }
+    }
+
+    // bit pattern passed to first pass in uniform
+    fn scale(self) -> u32 {
+        match self {
+            Map::ScaledSquare { scale } => scale,
+            _ => 0,
+        }
+    }
+}
+
+/// reduction
+/// Combines all elements of `x` with `op` in multiple passes, each pass reduces data
+/// by factor of workgroup size until single value is left, `None` for empty `x`
+pub async fn reduce<T: Element>(
+    ctx: &GpuContext,
+    op: ReduceOp,
+    x: &GpuBuffer<T>,
+) -> Result<Option<T>, Error> {
+    x.check_storage()?;
+    reduce_mapped(ctx, op, Map::Identity, x, None).await
+}
+
+/// arg reduction
+/// Index of smallest or largest element of `x`, ties are resolved to the first index,
+/// `None` for empty `x`
+pub async fn arg_reduce<T: Element>(
+    ctx: &GpuContext,
+    op: ArgReduceOp,
+    x: &GpuBuffer<T>,
+) -> Result<Option<usize>, Error> {
+    x.check_storage()?;
+    arg_reduce_mapped(ctx, op, Map::Identity, x).await
+}
+
+/// reduction of `map` applied to `x` (and `y` of same length for `Map::Product`)
+pub(crate) async fn reduce_mapped<T: Element>(
+    ctx: &GpuContext,
+    op: ReduceOp,
+    map: Map,
+    x: &GpuBuffer<T>,
+    y: Option<&GpuBuffer<T>>,
+) -> Result<Option<T>, Error> {
+    if x.is_empty() {
+        return Ok(None);
+    }
+
+    let source = specialise::<T>(&format!(
+        "{}{}{}",
+        op.source(),
+        map.source(),
+        include_str!("shader.wgsl")
+    ));
+    let first_pipeline = ctx.pipeline(&source, "reduce_first");
+    let partials_pipeline = ctx.pipeline(&source, "reduce_partials");
+
+    // partial results, passes ping-pong between these two
+    let len = workgroups(ctx, x.len());
+    let mut partials_in = GpuBuffer::<T>::zeroed(ctx, len, BufferUsages::STORAGE)?;
+    let mut partials_out = GpuBuffer::<T>::zeroed(ctx, len, BufferUsages::STORAGE)?;
+
+    // creates command encoder
+    // its role is to execute pipelines (one ore more)
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+
+    // first pass maps and reduces every share of input
+    let uniform_buffer_params = params(ctx, x.len(), map.scale())?;
+    let bind_group = match y {
+        Some(y) => create_bind_group(
+            &ctx.device,
+            &first_pipeline,
+            [
+                (0, x.as_binding()),
+                (1, y.as_binding()),
+                (2, uniform_buffer_params.as_binding()),
+                (3, partials_in.as_binding()),
+            ],
+        ),
+        None => create_bind_group(
+            &ctx.device,
+            &first_pipeline,
+            [
+                (0, x.as_binding()),
+                (2, uniform_buffer_params.as_binding()),
+                (3, partials_in.as_binding()),
+            ],
+        ),
+    };
+    dispatch(&mut encoder, &first_pipeline, &bind_group, len);
+
+    // following passes reduce partial results until one is left
+    let mut len = len;
+    while len > 1 {
+        let next_len = workgroups(ctx, len);
+        let uniform_buffer_params = params(ctx, len, 0)?;
+        let bind_group = create_bind_group(
+            &ctx.device,
+            &partials_pipeline,
+            [
+                (0, partials_in.slice(..len).as_binding()),
+                (2, uniform_buffer_params.as_binding()),
+                (3, partials_out.slice(..next_len).as_binding()),
+            ],
+        );
+        dispatch(&mut encoder, &partials_pipeline, &bind_group, next_len);
+
+        std::mem::swap(&mut partials_in, &mut partials_out);
+        len = next_len;
+    }
+
+    ctx.queue.submit(Some(encoder.finish()));
+
+    // copy result back to host
+    Ok(Some(partials_in.slice(..1).download().await?[0]))
+}
+
+/// arg reduction of `map` applied to `x`
+pub(crate) async fn arg_reduce_mapped<T: Element>(
+    ctx: &GpuContext,
+    op: ArgReduceOp,
+    map: Map,
+    x: &GpuBuffer<T>,
+) -> Result<Option<usize>, Error> {
+    if x.is_empty() {
+        return Ok(None);
+    }
+
+    let source = specialise::<T>(&format!(
+        "{}{}{}",
+        op.source(),
+        map.source(),
+        include_str!("arg.wgsl")
+    ));
+    let first_pipeline = ctx.pipeline(&source, "arg_reduce_first");
+    let partials_pipeline = ctx.pipeline(&source, "arg_reduce_partials");
+
+    // partial extremes and their indices, passes ping-pong between these
+    let len = workgroups(ctx, x.len());
+    let mut values_in = GpuBuffer::<T>::zeroed(ctx, len, BufferUsages::STORAGE)?;
+    let mut values_out = GpuBuffer::<T>::zeroed(ctx, len, BufferUsages::STORAGE)?;
+    let mut indices_in = GpuBuffer::<u32>::zeroed(ctx, len, BufferUsages::STORAGE)?;
+    let mut indices_out = GpuBuffer::<u32>::zeroed(ctx, len, BufferUsages::STORAGE)?;
+
+    // creates command encoder
+    // its role is to execute pipelines (one ore more)
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+
+    // first pass finds extreme of every share of input
+    let uniform_buffer_params = params(ctx, x.len(), map.scale())?;
+    let bind_group = create_bind_group(
+        &ctx.device,
+        &first_pipeline,
+        [
+            (0, x.as_binding()),
+            (2, uniform_buffer_params.as_binding()),
+            (3, values_in.as_binding()),
+            (4, indices_in.as_binding()),
+        ],
+    );
+    dispatch(&mut encoder, &first_pipeline, &bind_group, len);
+
+    // following passes reduce partial extremes until one is left
+    let mut len = len;
+    while len > 1 {
+        let next_len = workgroups(ctx, len);
+        let uniform_buffer_params = params(ctx, len, 0)?;
+        let bind_group = create_bind_group(
+            &ctx.device,
+            &partials_pipeline,
+            [
+                (0, values_in.slice(..len).as_binding()),
+                (2, uniform_buffer_params.as_binding()),
+                (3, values_out.slice(..next_len).as_binding()),
+                (4, indices_out.slice(..next_len).as_binding()),
+                (5, indices_in.slice(..len).as_binding()),
+            ],
+        );
+        dispatch(&mut encoder, &partials_pipeline, &bind_group, next_len);
+
+        std::mem::swap(&mut values_in, &mut values_out);
+        std::mem::swap(&mut indices_in, &mut indices_out);
+        len = next_len;
+    }
+
+    ctx.queue.submit(Some(encoder.finish()));
+
+    // copy result back to host
+    Ok(Some(indices_in.slice(..1).download().await?[0] as usize))
+}
+
+// workgroups of pass over `len` elements, every workgroup
+// loops over its share so dispatch fits into x dimension
+fn workgroups(ctx: &GpuContext, len: usize) -> usize {
+    let max_workgroups = ctx.device.limits().max_compute_workgroups_per_dimension as usize;
+    len.div_ceil(WORKGROUP_SIZE).min(max_workgroups)
+}
+
+fn params(ctx: &GpuContext, n: usize, scale: u32) -> Result<GpuBuffer<ReduceParams>, Error> {
+    let params = ReduceParams {
+        n: n as u32,
+        scale,
+        _pad: [0; 2],
+    };
+    GpuBuffer::from_slice(ctx, &[params], BufferUsages::UNIFORM)
+}
+
+fn dispatch(
+    encoder: &mut CommandEncoder,
+    compute_pipeline: &ComputePipeline,
+    bind_group: &BindGroup,
+    workgroups: usize,
+) {
+    let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+        label: None,
+        timestamp_writes: None,
+    });
+    cpass.set_pipeline(compute_pipeline);
+    cpass.set_bind_group(0, bind_group, &[]);
+    cpass.insert_debug_marker("reduction");
+    cpass.dispatch_workgroups(workgroups as u32, 1, 1);
+}
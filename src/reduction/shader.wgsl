@@ -0,0 +1,76 @@
+// T alias and functions below are prepended when pipeline is created:
+// map(i) reads i-th element of first pass, combine(a, b) is reduction operator
+// and identity(first) is its neutral element (idempotent operators use first element)
+@group(0)
+@binding(0)
+var<storage> x: array<T>;
+@group(0)
+@binding(1)
+var<storage> y: array<T>;
+@group(0)
+@binding(2)
+var<uniform> params: Params;
+@group(0)
+@binding(3)
+var<storage, read_write> out: array<T>;
+
+const WORKGROUP_SIZE: u32 = 256u;
+
+var<workgroup> partial: array<T, WORKGROUP_SIZE>;
+
+// tree reduction of partial results, result is written to out[workgroup]
+fn reduce(local: u32, workgroup: u32) {
+    for (var stride = WORKGROUP_SIZE / 2u; stride > 0u; stride /= 2u) {
+        workgroupBarrier();
+        if local < stride {
+            partial[local] = combine(partial[local], partial[local + stride]);
+        }
+    }
+
+    if local == 0u {
+        out[workgroup] = partial[0];
+    }
+}
+
+// first pass, every workgroup reduces mapped elements of its share of input
+@compute
+@workgroup_size(WORKGROUP_SIZE)
+fn reduce_first(
+    @builtin(local_invocation_index) local: u32,
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    var acc = identity(map(0u));
+    let stride = num_workgroups.x * WORKGROUP_SIZE;
+    for (var i = workgroup_id.x * WORKGROUP_SIZE + local; i < params.n; i += stride) {
+        acc = combine(acc, map(i));
+    }
+    partial[local] = acc;
+
+    reduce(local, workgroup_id.x);
+}
+
+// following passes, reduce partial results of previous pass stored in x
+@compute
+@workgroup_size(WORKGROUP_SIZE)
+fn reduce_partials(
+    @builtin(local_invocation_index) local: u32,
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    var acc = identity(x[0]);
+    let stride = num_workgroups.x * WORKGROUP_SIZE;
+    for (var i = workgroup_id.x * WORKGROUP_SIZE + local; i < params.n; i += stride) {
+        acc = combine(acc, x[i]);
+    }
+    partial[local] = acc;
+
+    reduce(local, workgroup_id.x);
+}
+
+struct Params {
+    // number of elements, or partial results in following passes
+    n: u32,
+    // bit pattern of divisor of `Map::ScaledSquare`
+    scale: u32,
+}
@@ -1,55 +1,184 @@
-use wgpu::{BufferUsages, Device, Queue};
+use bytemuck::{Pod, Zeroable};
+use wgpu::BufferUsages;
 
 use crate::{
-    helpers::{create_bind_group, create_pipeline, create_staging_buffer, create_storage_buffer},
-    Error,
+    buffer::GpuBuffer,
+    element::{specialise, to_bits, Element},
+    helpers::{create_bind_group, dispatch_size, with_dispatch},
+    Error, GpuContext,
 };
 
-// executes shader with given parameters
-async fn execute_shader(
-    a: i32,
-    x: &[i32],
-    y: &[i32],
-    device: &Device,
-    queue: &Queue,
-) -> Result<Vec<i32>, Error> {
-    let size_x = size_of_val(x) as wgpu::BufferAddress;
-    // return buffer
-    // MAP_READ allows for reading it
-    // COPY_DST allows for it to be desetination of cpy
-    let staging_buffer_x = create_staging_buffer(device, size_x);
+// has to match WORKGROUP_SIZE in shader
+const WORKGROUP_SIZE: u32 = 64;
+
+#[repr(C)]
+#[derive(Clone, Copy, Zeroable, Pod)]
+pub(crate) struct SaxpyParams {
+    n: u32,
+    incx: u32,
+    incy: u32,
+    // bit pattern of element type
+    a: u32,
+}
 
-    // buffer that is avaliable for GPU
-    let storage_buffer_x = create_storage_buffer(
-        device,
-        x,
-        BufferUsages::STORAGE | BufferUsages::COPY_DST | BufferUsages::COPY_SRC,
-    );
+impl SaxpyParams {
+    pub(crate) fn new<T: Element>(n: usize, a: T, incx: usize, incy: usize) -> SaxpyParams {
+        SaxpyParams {
+            n: n as u32,
+            incx: incx as u32,
+            incy: incy as u32,
+            a: to_bits(a),
+        }
+    }
+}
 
-    // buffer that is avaliable for GPU
-    let storage_buffer_y = create_storage_buffer(device, y, BufferUsages::STORAGE);
+/// saxpy
+/// Computes `a * x + y` element wise into new vector, `x` and `y` are left untouched
+pub async fn saxpy<T: Element>(ctx: &GpuContext, a: T, x: &[T], y: &[T]) -> Result<Vec<T>, Error> {
+    if x.len() != y.len() {
+        return Err(Error::LengthMismatch {
+            expected: x.len(),
+            found: y.len(),
+        });
+    }
+    saxpy_strided(ctx, x.len(), a, x, 1, y, 1).await
+}
+
+/// strided saxpy
+/// Computes `a * x[i * incx] + y[i * incy]` for `n` elements into new vector
+pub async fn saxpy_strided<T: Element>(
+    ctx: &GpuContext,
+    n: usize,
+    a: T,
+    x: &[T],
+    incx: usize,
+    y: &[T],
+    incy: usize,
+) -> Result<Vec<T>, Error> {
+    check_stride(n, x, incx)?;
+    check_stride(n, y, incy)?;
+    if n == 0 {
+        return Ok(Vec::new());
+    }
+
+    // output buffer that is avaliable for GPU
+    let storage_buffer_out = GpuBuffer::zeroed(ctx, n, BufferUsages::STORAGE)?;
+
+    // buffers that are avaliable for GPU
+    let storage_buffer_x = GpuBuffer::from_slice(ctx, x, BufferUsages::STORAGE)?;
+    let storage_buffer_y = GpuBuffer::from_slice(ctx, y, BufferUsages::STORAGE)?;
+
+    let params = SaxpyParams::new(n, a, incx, incy);
+    execute_saxpy(
+        ctx,
+        params,
+        &storage_buffer_x,
+        &storage_buffer_y,
+        Some(&storage_buffer_out),
+    )?;
+
+    // copy result back to host
+    storage_buffer_out.download().await
+}
+
+/// axpy
+/// Computes `y[i * incy] = a * x[i * incx] + y[i * incy]` for `n` elements in place,
+/// following BLAS convention
+pub async fn axpy<T: Element>(
+    ctx: &GpuContext,
+    n: usize,
+    a: T,
+    x: &[T],
+    incx: usize,
+    y: &mut [T],
+    incy: usize,
+) -> Result<(), Error> {
+    check_stride(n, x, incx)?;
+    check_stride(n, y, incy)?;
+    if n == 0 {
+        return Ok(());
+    }
+
+    // buffers that are avaliable for GPU, y is read and written by shader
+    let storage_buffer_x = GpuBuffer::from_slice(ctx, x, BufferUsages::STORAGE)?;
+    let storage_buffer_y = GpuBuffer::from_slice(ctx, y, BufferUsages::STORAGE)?;
+
+    let params = SaxpyParams::new(n, a, incx, incy);
+    execute_saxpy(ctx, params, &storage_buffer_x, &storage_buffer_y, None)?;
+
+    // copy result back to host
+    y.copy_from_slice(&storage_buffer_y.download().await?);
+    Ok(())
+}
+
+// `n` elements with stride `inc` have to fit into `data`
+fn check_stride<T>(n: usize, data: &[T], inc: usize) -> Result<(), Error> {
+    if inc == 0 {
+        return Err(Error::InvalidInput("stride has to be positive".to_owned()));
+    }
+    let expected = match n {
+        0 => 0,
+        n => (n - 1) * inc + 1,
+    };
+    if data.len() < expected {
+        return Err(Error::LengthMismatch {
+            expected,
+            found: data.len(),
+        });
+    }
+    Ok(())
+}
 
+// dispatches saxpy over `params.n` elements,
+// result is written to `storage_buffer_out` or in place to y if there is none
+pub(crate) fn execute_saxpy<T: Element>(
+    ctx: &GpuContext,
+    params: SaxpyParams,
+    storage_buffer_x: &GpuBuffer<T>,
+    storage_buffer_y: &GpuBuffer<T>,
+    storage_buffer_out: Option<&GpuBuffer<T>>,
+) -> Result<(), Error> {
     // buffer that is avaliable for GPU
-    let storage_buffer_a = create_storage_buffer(device, &[a], BufferUsages::STORAGE);
+    let uniform_buffer_params = GpuBuffer::from_slice(ctx, &[params], BufferUsages::UNIFORM)?;
 
-    // creation of compute pipeline with entrypoint "main"
-    let compute_pipeline = create_pipeline(device, include_str!("shader.wgsl"), "main");
+    // creation of compute pipeline, entry point decides where result goes
+    let source = specialise::<T>(&with_dispatch(include_str!("shader.wgsl")));
+    let compute_pipeline = match storage_buffer_out {
+        Some(_) => ctx.pipeline(&source, "main"),
+        None => ctx.pipeline(&source, "in_place"),
+    };
 
     // binding buffer to group zero with specific bindings
-    let bind_group = create_bind_group(
-        device,
-        &compute_pipeline,
-        [
-            (0, storage_buffer_x.as_entire_binding()),
-            (1, storage_buffer_y.as_entire_binding()),
-            (2, storage_buffer_a.as_entire_binding()),
-        ],
-    );
+    let bind_group = match storage_buffer_out {
+        Some(storage_buffer_out) => create_bind_group(
+            &ctx.device,
+            &compute_pipeline,
+            [
+                (0, storage_buffer_x.as_binding()),
+                (1, storage_buffer_y.as_binding()),
+                (2, storage_buffer_out.as_binding()),
+                (3, uniform_buffer_params.as_binding()),
+            ],
+        ),
+        None => create_bind_group(
+            &ctx.device,
+            &compute_pipeline,
+            [
+                (0, storage_buffer_x.as_binding()),
+                (1, storage_buffer_y.as_binding()),
+                (3, uniform_buffer_params.as_binding()),
+            ],
+        ),
+    };
+
+    // workgroups beyond dispatch limit continue in y and z dimension
+    let workgroups = dispatch_size(params.n.div_ceil(WORKGROUP_SIZE), &ctx.device.limits());
 
     // creates command encoder
     // its role is to execute pipelines (one ore more)
-    let mut encoder =
-        device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
 
     // compute pass is invoked in other scope,
     // it needs to be dealocated before we can use encoder again
@@ -61,45 +190,9 @@ async fn execute_shader(
         cpass.set_pipeline(&compute_pipeline);
         cpass.set_bind_group(0, &bind_group, &[]);
         cpass.insert_debug_marker("saxpy");
-        cpass.dispatch_workgroups(x.len() as u32, 1, 1);
-    }
-
-    // copy result
-    encoder.copy_buffer_to_buffer(&storage_buffer_x, 0, &staging_buffer_x, 0, size_x);
-
-    queue.submit(Some(encoder.finish()));
-
-    // sending data back to host
-    let buffer_slice = staging_buffer_x.slice(..);
-
-    let (sender, receiver) = flume::bounded(1);
-    buffer_slice.map_async(wgpu::MapMode::Read, move |v| sender.send(v).unwrap());
-
-    // await for GPU processes
-    device.poll(wgpu::Maintain::wait()).panic_on_timeout();
-
-    // await for buffer
-    if let Ok(Ok(())) = receiver.recv_async().await {
-        let data = buffer_slice.get_mapped_range();
-        let result: Vec<i32> = bytemuck::cast_slice(&data).to_vec();
-        // all veiws have to be dropped manualy
-        drop(data);
-        staging_buffer_x.unmap();
-
-        return Ok(result);
+        cpass.dispatch_workgroups(workgroups.0, workgroups.1, workgroups.2);
     }
 
-    Err(Error::ExecutionError)
-}
-
-pub async fn execute_saxpy(device: Device, queue: Queue) -> Result<(), Error> {
-    let x = [1, 2, 3, 4];
-    let y = [4, 3, 2, 1];
-    let a = 10;
-
-    let result = execute_shader(a, &x, &y, &device, &queue).await?;
-
-    println!("a: {}, x: {:?}, y: {:?}", a, x, y);
-    println!("{:?}", result);
+    ctx.queue.submit(Some(encoder.finish()));
     Ok(())
 }
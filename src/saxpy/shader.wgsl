@@ -1,20 +1,60 @@
 @group(0)
 @binding(0)
-var<storage, read_write> x: array<i32>; 
+var<storage> x: array<T>;
 @group(0)
 @binding(1)
-var<storage> y: array<i32>;
+var<storage, read_write> y: array<T>;
 @group(0)
 @binding(2)
-var<storage> a: array<i32>;
+var<storage, read_write> out: array<T>;
+@group(0)
+@binding(3)
+var<uniform> params: Params;
+
+const WORKGROUP_SIZE: u32 = 64u;
 
-fn saxpy(x: i32, y: i32) -> i32 {
-    var result = (a[0]*x) + y;
-    return result;
+fn saxpy(i: u32) -> T {
+    let a = bitcast<T>(params.a);
+    return a * x[i * params.incx] + y[i * params.incy];
 }
 
+// element handled by invocation
+fn element(local: u32, workgroup_id: vec3<u32>, num_workgroups: vec3<u32>) -> u32 {
+    return workgroup_index(workgroup_id, num_workgroups) * WORKGROUP_SIZE + local;
+}
+
+// result is written to separate out
 @compute
-@workgroup_size(1)
-fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
-    x[global_id.x] = saxpy(x[global_id.x], y[global_id.x]);
+@workgroup_size(WORKGROUP_SIZE)
+fn main(
+    @builtin(local_invocation_index) local: u32,
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    let i = element(local, workgroup_id, num_workgroups);
+    if i < params.n {
+        out[i] = saxpy(i);
+    }
+}
+
+// result overwrites y, as in BLAS
+@compute
+@workgroup_size(WORKGROUP_SIZE)
+fn in_place(
+    @builtin(local_invocation_index) local: u32,
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    let i = element(local, workgroup_id, num_workgroups);
+    if i < params.n {
+        y[i * params.incy] = saxpy(i);
+    }
+}
+
+struct Params {
+    n: u32,
+    incx: u32,
+    incy: u32,
+    // bit pattern of T
+    a: u32,
 }
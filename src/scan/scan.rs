@@ -0,0 +1,178 @@
+use bytemuck::{Pod, Zeroable};
+use wgpu::{BufferUsages, CommandEncoder};
+
+use crate::{
+    buffer::GpuBuffer,
+    element::{specialise, Element},
+    helpers::{create_bind_group, dispatch_size, with_dispatch},
+    Error, GpuContext,
+};
+
+// has to match BLOCK in shader
+const BLOCK: usize = 512;
+
+#[repr(C)]
+#[derive(Clone, Copy, Zeroable, Pod)]
+struct ScanParams {
+    n: u32,
+    inclusive: u32,
+    _pad: [u32; 2],
+}
+
+/// scan mode
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ScanMode {
+    /// `out[i]` includes `x[i]`
+    #[default]
+    Inclusive,
+    /// `out[i]` is sum of elements before `x[i]`, `out[0]` is zero
+    Exclusive,
+}
+
+/// prefix scan
+/// Computes running sums of `x`, see `scan_buffers`
+pub async fn scan<T: Element>(ctx: &GpuContext, mode: ScanMode, x: &[T]) -> Result<Vec<T>, Error> {
+    // buffers that are avaliable for GPU
+    let storage_buffer_x = GpuBuffer::from_slice(ctx, x, BufferUsages::STORAGE)?;
+    let storage_buffer_out = GpuBuffer::zeroed(ctx, x.len(), BufferUsages::STORAGE)?;
+
+    scan_buffers(ctx, mode, &storage_buffer_x, &storage_buffer_out)?;
+
+    // copy result back to host
+    storage_buffer_out.download().await
+}
+
+/// prefix scan of vector already stored on GPU
+/// Work efficient Blelloch scan, blocks of 512 elements are scanned in workgroup memory,
+/// block totals are scanned recursively and added back to blocks.
+/// Floating point sums are associated differently than in sequential scan.
+/// `x` and `out` may be the same buffer, in place scan goes through temporary buffer
+pub fn scan_buffers<T: Element>(
+    ctx: &GpuContext,
+    mode: ScanMode,
+    x: &GpuBuffer<T>,
+    out: &GpuBuffer<T>,
+) -> Result<(), Error> {
+    x.check_storage()?;
+    out.check_storage()?;
+    if x.len() != out.len() {
+        return Err(Error::LengthMismatch {
+            expected: x.len(),
+            found: out.len(),
+        });
+    }
+    if x.is_empty() {
+        return Ok(());
+    }
+
+    // creates command encoder
+    // its role is to execute pipelines (one ore more)
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+    // buffer can't be bound as input and output at once
+    if std::ptr::eq(x, out) {
+        let storage_buffer_tmp = GpuBuffer::<T>::zeroed(ctx, x.len(), BufferUsages::STORAGE)?;
+        encode_scan(ctx, &mut encoder, mode, x, &storage_buffer_tmp)?;
+        encoder.copy_buffer_to_buffer(storage_buffer_tmp.buffer(), 0, out.buffer(), 0, out.size());
+    } else {
+        encode_scan(ctx, &mut encoder, mode, x, out)?;
+    }
+    ctx.queue.submit(Some(encoder.finish()));
+
+    Ok(())
+}
+
+/// records scan of non-empty `x` into `out` to `encoder`
+pub(crate) fn encode_scan<T: Element>(
+    ctx: &GpuContext,
+    encoder: &mut CommandEncoder,
+    mode: ScanMode,
+    x: &GpuBuffer<T>,
+    out: &GpuBuffer<T>,
+) -> Result<(), Error> {
+    let source = specialise::<T>(&with_dispatch(include_str!("shader.wgsl")));
+    let scan_pipeline = ctx.pipeline(&source, "scan_blocks");
+    let add_pipeline = ctx.pipeline(&source, "add_offsets");
+
+    // level k scans its input into output and block totals into sums,
+    // totals are exclusively scanned by level k + 1 into offsets[k]
+    let mut levels = vec![x.len()];
+    while levels[levels.len() - 1] > BLOCK {
+        levels.push(levels[levels.len() - 1].div_ceil(BLOCK));
+    }
+
+    let params: Vec<_> = levels
+        .iter()
+        .enumerate()
+        .map(|(level, &len)| {
+            let params = ScanParams {
+                n: len as u32,
+                inclusive: (level == 0 && mode == ScanMode::Inclusive) as u32,
+                _pad: [0; 2],
+            };
+            GpuBuffer::from_slice(ctx, &[params], BufferUsages::UNIFORM)
+        })
+        .collect::<Result<_, _>>()?;
+    let sums: Vec<_> = levels
+        .iter()
+        .map(|len| GpuBuffer::<T>::zeroed(ctx, len.div_ceil(BLOCK), BufferUsages::STORAGE))
+        .collect::<Result<_, _>>()?;
+    let offsets: Vec<_> = levels[1..]
+        .iter()
+        .map(|&len| GpuBuffer::<T>::zeroed(ctx, len, BufferUsages::STORAGE))
+        .collect::<Result<_, _>>()?;
+
+    let level_input = |level: usize| if level == 0 { x } else { &sums[level - 1] };
+    let level_output = |level: usize| if level == 0 { out } else { &offsets[level - 1] };
+
+    // scan blocks of every level, top level has single block
+    for level in 0..levels.len() {
+        let bind_group = create_bind_group(
+            &ctx.device,
+            &scan_pipeline,
+            [
+                (0, level_input(level).as_binding()),
+                (1, level_output(level).as_binding()),
+                (2, sums[level].as_binding()),
+                (3, params[level].as_binding()),
+            ],
+        );
+        dispatch_blocks(ctx, encoder, &scan_pipeline, &bind_group, sums[level].len());
+    }
+
+    // add scanned block totals back, from top level down
+    for level in (0..levels.len() - 1).rev() {
+        let bind_group = create_bind_group(
+            &ctx.device,
+            &add_pipeline,
+            [
+                (1, level_output(level).as_binding()),
+                (3, params[level].as_binding()),
+                (4, level_output(level + 1).as_binding()),
+            ],
+        );
+        dispatch_blocks(ctx, encoder, &add_pipeline, &bind_group, sums[level].len());
+    }
+    Ok(())
+}
+
+// one workgroup per block, blocks beyond dispatch limit continue in y and z dimension
+fn dispatch_blocks(
+    ctx: &GpuContext,
+    encoder: &mut CommandEncoder,
+    compute_pipeline: &wgpu::ComputePipeline,
+    bind_group: &wgpu::BindGroup,
+    blocks: usize,
+) {
+    let workgroups = dispatch_size(blocks as u32, &ctx.device.limits());
+
+    let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+        label: None,
+        timestamp_writes: None,
+    });
+    cpass.set_pipeline(compute_pipeline);
+    cpass.set_bind_group(0, bind_group, &[]);
+    cpass.insert_debug_marker("prefix scan");
+    cpass.dispatch_workgroups(workgroups.0, workgroups.1, workgroups.2);
+}
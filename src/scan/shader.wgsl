@@ -0,0 +1,114 @@
+@group(0)
+@binding(0)
+var<storage> x: array<T>;
+@group(0)
+@binding(1)
+var<storage, read_write> out: array<T>;
+@group(0)
+@binding(2)
+var<storage, read_write> sums: array<T>;
+@group(0)
+@binding(3)
+var<uniform> params: Params;
+@group(0)
+@binding(4)
+var<storage> offsets: array<T>;
+
+const WORKGROUP_SIZE: u32 = 256u;
+// every invocation handles two elements of block
+const BLOCK: u32 = 512u;
+
+var<workgroup> temp: array<T, BLOCK>;
+
+// Blelloch scan of one block in workgroup memory, exclusive (or inclusive)
+// scan of block is written to out and its total to sums[block]
+@compute
+@workgroup_size(WORKGROUP_SIZE)
+fn scan_blocks(
+    @builtin(local_invocation_index) local: u32,
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    let block = workgroup_index(workgroup_id, num_workgroups);
+    let base = block * BLOCK;
+
+    // elements past the end are zero, so they don't change sums
+    for (var j = 0u; j < 2u; j += 1u) {
+        let i = base + 2u * local + j;
+        if i < params.n {
+            temp[2u * local + j] = x[i];
+        } else {
+            temp[2u * local + j] = T(0);
+        }
+    }
+
+    // up-sweep builds sums of growing subtrees in place
+    var offset = 1u;
+    for (var d = BLOCK / 2u; d > 0u; d /= 2u) {
+        workgroupBarrier();
+        if local < d {
+            let ai = offset * (2u * local + 1u) - 1u;
+            let bi = offset * (2u * local + 2u) - 1u;
+            temp[bi] += temp[ai];
+        }
+        offset *= 2u;
+    }
+
+    workgroupBarrier();
+    if local == 0u {
+        if base < params.n {
+            sums[block] = temp[BLOCK - 1u];
+        }
+        temp[BLOCK - 1u] = T(0);
+    }
+
+    // down-sweep distributes prefixes back to leaves
+    for (var d = 1u; d < BLOCK; d *= 2u) {
+        offset /= 2u;
+        workgroupBarrier();
+        if local < d {
+            let ai = offset * (2u * local + 1u) - 1u;
+            let bi = offset * (2u * local + 2u) - 1u;
+            let t = temp[ai];
+            temp[ai] = temp[bi];
+            temp[bi] += t;
+        }
+    }
+    workgroupBarrier();
+
+    for (var j = 0u; j < 2u; j += 1u) {
+        let i = base + 2u * local + j;
+        if i < params.n {
+            if params.inclusive != 0u {
+                out[i] = temp[2u * local + j] + x[i];
+            } else {
+                out[i] = temp[2u * local + j];
+            }
+        }
+    }
+}
+
+// adds scanned totals of preceding blocks to every element of block
+@compute
+@workgroup_size(WORKGROUP_SIZE)
+fn add_offsets(
+    @builtin(local_invocation_index) local: u32,
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    let block = workgroup_index(workgroup_id, num_workgroups);
+    let base = block * BLOCK;
+
+    for (var j = 0u; j < 2u; j += 1u) {
+        let i = base + 2u * local + j;
+        if i < params.n {
+            out[i] += offsets[block];
+        }
+    }
+}
+
+struct Params {
+    // number of elements of this level
+    n: u32,
+    inclusive: u32,
+}
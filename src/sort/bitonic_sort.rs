@@ -0,0 +1,108 @@
+use bytemuck::{Pod, Zeroable};
+use wgpu::BufferUsages;
+
+use crate::{
+    buffer::GpuBuffer,
+    element::{specialise, Element},
+    helpers::{create_bind_group, dispatch_size, with_dispatch},
+    matrix::Matrix,
+    Error, GpuContext,
+};
+
+// longest row sorted in workgroup memory
+const MAX_COLS: usize = 4096;
+
+#[repr(C)]
+#[derive(Clone, Copy, Zeroable, Pod)]
+struct BitonicParams {
+    rows: u32,
+    cols: u32,
+    _pad: [u32; 2],
+}
+
+/// bitonic sort
+/// Sorts up to 4096 elements in ascending order within workgroup memory of single workgroup.
+/// Order of NaNs is unspecified
+pub async fn bitonic_sort<T: Element>(ctx: &GpuContext, data: &[T]) -> Result<Vec<T>, Error> {
+    let matrix = Matrix::new(data.to_vec(), 1, data.len())?;
+    Ok(bitonic_sort_rows(ctx, &matrix).await?.into_data())
+}
+
+/// segmented bitonic sort
+/// Sorts every row of `matrix` with up to 4096 columns independently in single dispatch,
+/// one workgroup per row
+pub async fn bitonic_sort_rows<T: Element>(
+    ctx: &GpuContext,
+    matrix: &Matrix<T>,
+) -> Result<Matrix<T>, Error> {
+    let (rows, cols) = (matrix.rows(), matrix.cols());
+    if cols > MAX_COLS {
+        return Err(Error::InvalidInput(format!(
+            "bitonic sort handles rows of up to {MAX_COLS} elements, found {cols}"
+        )));
+    }
+    // row is padded to power of two in workgroup memory
+    let padded = cols.next_power_of_two().max(2);
+    if padded * size_of::<T>() > ctx.device.limits().max_compute_workgroup_storage_size as usize {
+        return Err(Error::InvalidInput(format!(
+            "row of {cols} elements exceeds workgroup storage of device"
+        )));
+    }
+    if rows * cols == 0 {
+        return Ok(matrix.clone());
+    }
+
+    // buffers that are avaliable for GPU, rows are sorted in place
+    let storage_buffer_data = GpuBuffer::from_slice(ctx, matrix.data(), BufferUsages::STORAGE)?;
+    let params = BitonicParams {
+        rows: rows as u32,
+        cols: cols as u32,
+        _pad: [0; 2],
+    };
+    let uniform_buffer_params = GpuBuffer::from_slice(ctx, &[params], BufferUsages::UNIFORM)?;
+
+    // creation of compute pipeline with entrypoint "main",
+    // workgroup array is sized for padded row, so every row length gets its own pipeline
+    let source = specialise::<T>(&with_dispatch(&format!(
+        "const COLS: u32 = {padded}u;\n{}",
+        include_str!("bitonic_sort.wgsl")
+    )));
+    let compute_pipeline = ctx.pipeline(&source, "main");
+
+    // binding buffer to group zero with specific bindings
+    let bind_group = create_bind_group(
+        &ctx.device,
+        &compute_pipeline,
+        [
+            (0, storage_buffer_data.as_binding()),
+            (1, uniform_buffer_params.as_binding()),
+        ],
+    );
+
+    // one workgroup per row, rows beyond dispatch limit continue in y and z dimension
+    let workgroups = dispatch_size(rows as u32, &ctx.device.limits());
+
+    // creates command encoder
+    // its role is to execute pipelines (one ore more)
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+
+    // compute pass is invoked in other scope,
+    // it needs to be dealocated before we can use encoder again
+    {
+        let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: None,
+            timestamp_writes: None,
+        });
+        cpass.set_pipeline(&compute_pipeline);
+        cpass.set_bind_group(0, &bind_group, &[]);
+        cpass.insert_debug_marker("bitonic sort");
+        cpass.dispatch_workgroups(workgroups.0, workgroups.1, workgroups.2);
+    }
+
+    ctx.queue.submit(Some(encoder.finish()));
+
+    // copy result back to host
+    Matrix::new(storage_buffer_data.download().await?, rows, cols)
+}
@@ -0,0 +1,74 @@
+// T alias and COLS constant (row length rounded up to power of two)
+// are prepended when pipeline is created
+@group(0)
+@binding(0)
+var<storage, read_write> data: array<T>;
+@group(0)
+@binding(1)
+var<uniform> params: Params;
+
+const WORKGROUP_SIZE: u32 = 256u;
+
+var<workgroup> row: array<T, COLS>;
+
+// orders pair of row elements, elements past the end act as infinitely large,
+// so comparisons with them never swap and can be skipped
+fn compare_exchange(i: u32, partner: u32) {
+    if partner < params.cols && row[partner] < row[i] {
+        let value = row[i];
+        row[i] = row[partner];
+        row[partner] = value;
+    }
+}
+
+// every workgroup sorts one row in workgroup memory with bitonic network,
+// first step of every merge compares mirrored elements, so all comparisons are ascending
+@compute
+@workgroup_size(WORKGROUP_SIZE)
+fn main(
+    @builtin(local_invocation_index) local: u32,
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    let row_index = workgroup_index(workgroup_id, num_workgroups);
+    if row_index >= params.rows {
+        return;
+    }
+    let base = row_index * params.cols;
+
+    for (var c = local; c < params.cols; c += WORKGROUP_SIZE) {
+        row[c] = data[base + c];
+    }
+
+    // network is built for row padded to power of two
+    let pairs = COLS / 2u;
+
+    for (var block = 2u; block <= COLS; block *= 2u) {
+        // merge two sorted halves of block, mirrored
+        var half = block / 2u;
+        workgroupBarrier();
+        for (var t = local; t < pairs; t += WORKGROUP_SIZE) {
+            let i = (t / half) * block + t % half;
+            compare_exchange(i, i ^ (block - 1u));
+        }
+
+        // bitonic clean up with halving distance
+        for (half = block / 4u; half > 0u; half /= 2u) {
+            workgroupBarrier();
+            for (var t = local; t < pairs; t += WORKGROUP_SIZE) {
+                let i = (t / half) * 2u * half + t % half;
+                compare_exchange(i, i + half);
+            }
+        }
+    }
+    workgroupBarrier();
+
+    for (var c = local; c < params.cols; c += WORKGROUP_SIZE) {
+        data[base + c] = row[c];
+    }
+}
+
+struct Params {
+    rows: u32,
+    cols: u32,
+}
@@ -0,0 +1,2 @@
+pub mod bitonic_sort;
+pub mod radix_sort;
@@ -0,0 +1,198 @@
+use bytemuck::{Pod, Zeroable};
+use wgpu::BufferUsages;
+
+use crate::{
+    buffer::GpuBuffer,
+    helpers::{create_bind_group, dispatch_size, with_dispatch},
+    scan::scan::{encode_scan, ScanMode},
+    Error, GpuContext,
+};
+
+// has to match WORKGROUP_SIZE and RADIX in shader
+const WORKGROUP_SIZE: usize = 256;
+const RADIX: usize = 16;
+const DIGIT_BITS: u32 = 4;
+
+#[repr(C)]
+#[derive(Clone, Copy, Zeroable, Pod)]
+struct RadixParams {
+    n: u32,
+    shift: u32,
+    blocks: u32,
+    _pad: u32,
+}
+
+// WGSL of sorted element
+const KEYS: &str = "alias E = u32;\nfn key_of(e: E) -> u32 { return e; }\n";
+const PAIRS: &str = "alias E = vec2<u32>;\nfn key_of(e: E) -> u32 { return e.x; }\n";
+
+/// radix sort
+/// Sorts `u32` keys in ascending order with LSD radix sort,
+/// every pass sorts by 4 bits using histogram, its scan and stable scatter
+pub async fn radix_sort(ctx: &GpuContext, keys: &[u32]) -> Result<Vec<u32>, Error> {
+    sort_elements(ctx, KEYS, keys).await
+}
+
+/// radix sort of key-value pairs
+/// Sorts `values` by `keys`, pairs with equal keys keep their order
+pub async fn radix_sort_pairs(
+    ctx: &GpuContext,
+    keys: &[u32],
+    values: &[u32],
+) -> Result<(Vec<u32>, Vec<u32>), Error> {
+    if keys.len() != values.len() {
+        return Err(Error::LengthMismatch {
+            expected: keys.len(),
+            found: values.len(),
+        });
+    }
+
+    // pairs are interleaved, so scatter needs only one storage buffer for them
+    let pairs: Vec<[u32; 2]> = keys.iter().zip(values).map(|(&k, &v)| [k, v]).collect();
+    let sorted = sort_elements(ctx, PAIRS, &pairs).await?;
+
+    Ok(sorted.into_iter().map(|[k, v]| (k, v)).unzip())
+}
+
+/// radix sort of `f32` keys
+/// Keys are bit-flipped to `u32` which order like `f32::total_cmp`,
+/// so negative zero goes before zero and NaNs go to the ends
+pub async fn radix_sort_f32(ctx: &GpuContext, keys: &[f32]) -> Result<Vec<f32>, Error> {
+    let keys: Vec<u32> = keys.iter().map(|&key| f32_to_key(key)).collect();
+    let sorted = radix_sort(ctx, &keys).await?;
+    Ok(sorted.into_iter().map(key_to_f32).collect())
+}
+
+/// radix sort of key-value pairs with `f32` keys, see `radix_sort_f32`
+pub async fn radix_sort_pairs_f32(
+    ctx: &GpuContext,
+    keys: &[f32],
+    values: &[u32],
+) -> Result<(Vec<f32>, Vec<u32>), Error> {
+    let keys: Vec<u32> = keys.iter().map(|&key| f32_to_key(key)).collect();
+    let (keys, values) = radix_sort_pairs(ctx, &keys, values).await?;
+    Ok((keys.into_iter().map(key_to_f32).collect(), values))
+}
+
+// negative floats have all bits flipped, so larger magnitude sorts first,
+// positive floats only sign bit, so they sort after negative ones
+fn f32_to_key(value: f32) -> u32 {
+    let bits = value.to_bits();
+    if bits >> 31 == 1 {
+        !bits
+    } else {
+        bits | 1 << 31
+    }
+}
+
+fn key_to_f32(key: u32) -> f32 {
+    if key >> 31 == 1 {
+        f32::from_bits(key & !(1 << 31))
+    } else {
+        f32::from_bits(!key)
+    }
+}
+
+// sorts elements described by `element_source`, key is first word of element
+async fn sort_elements<E: Pod>(
+    ctx: &GpuContext,
+    element_source: &str,
+    elements: &[E],
+) -> Result<Vec<E>, Error> {
+    if elements.len() < 2 {
+        return Ok(elements.to_vec());
+    }
+
+    let source = with_dispatch(&format!(
+        "{element_source}{}",
+        include_str!("radix_sort.wgsl")
+    ));
+    let count_pipeline = ctx.pipeline(&source, "count_digits");
+    let scatter_pipeline = ctx.pipeline(&source, "scatter");
+
+    let blocks = elements.len().div_ceil(WORKGROUP_SIZE);
+    let workgroups = dispatch_size(blocks as u32, &ctx.device.limits());
+
+    // elements ping-pong between these two, every pass scatters into the other one
+    let mut storage_buffer_in = GpuBuffer::from_slice(ctx, elements, BufferUsages::STORAGE)?;
+    let mut storage_buffer_out =
+        GpuBuffer::<E>::zeroed(ctx, elements.len(), BufferUsages::STORAGE)?;
+
+    // digit counts of every block and their exclusive scan
+    let storage_buffer_histogram =
+        GpuBuffer::<u32>::zeroed(ctx, RADIX * blocks, BufferUsages::STORAGE)?;
+    let storage_buffer_offsets =
+        GpuBuffer::<u32>::zeroed(ctx, RADIX * blocks, BufferUsages::STORAGE)?;
+
+    // creates command encoder
+    // its role is to execute pipelines (one ore more)
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+
+    for shift in (0..u32::BITS).step_by(DIGIT_BITS as usize) {
+        let params = RadixParams {
+            n: elements.len() as u32,
+            shift,
+            blocks: blocks as u32,
+            _pad: 0,
+        };
+        let uniform_buffer_params = GpuBuffer::from_slice(ctx, &[params], BufferUsages::UNIFORM)?;
+
+        let bind_group = create_bind_group(
+            &ctx.device,
+            &count_pipeline,
+            [
+                (0, storage_buffer_in.as_binding()),
+                (1, storage_buffer_histogram.as_binding()),
+                (2, uniform_buffer_params.as_binding()),
+            ],
+        );
+        {
+            let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: None,
+                timestamp_writes: None,
+            });
+            cpass.set_pipeline(&count_pipeline);
+            cpass.set_bind_group(0, &bind_group, &[]);
+            cpass.insert_debug_marker("radix sort digit count");
+            cpass.dispatch_workgroups(workgroups.0, workgroups.1, workgroups.2);
+        }
+
+        encode_scan(
+            ctx,
+            &mut encoder,
+            ScanMode::Exclusive,
+            &storage_buffer_histogram,
+            &storage_buffer_offsets,
+        )?;
+
+        let bind_group = create_bind_group(
+            &ctx.device,
+            &scatter_pipeline,
+            [
+                (0, storage_buffer_in.as_binding()),
+                (2, uniform_buffer_params.as_binding()),
+                (3, storage_buffer_offsets.as_binding()),
+                (4, storage_buffer_out.as_binding()),
+            ],
+        );
+        {
+            let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: None,
+                timestamp_writes: None,
+            });
+            cpass.set_pipeline(&scatter_pipeline);
+            cpass.set_bind_group(0, &bind_group, &[]);
+            cpass.insert_debug_marker("radix sort scatter");
+            cpass.dispatch_workgroups(workgroups.0, workgroups.1, workgroups.2);
+        }
+
+        std::mem::swap(&mut storage_buffer_in, &mut storage_buffer_out);
+    }
+
+    ctx.queue.submit(Some(encoder.finish()));
+
+    // copy result back to host
+    storage_buffer_in.download().await
+}
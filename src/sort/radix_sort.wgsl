@@ -0,0 +1,123 @@
+// E alias of sorted element and key_of(e) are prepended when pipeline is created,
+// element is either key alone or key with payload
+@group(0)
+@binding(0)
+var<storage> elements: array<E>;
+@group(0)
+@binding(1)
+var<storage, read_write> histogram: array<u32>;
+@group(0)
+@binding(2)
+var<uniform> params: Params;
+@group(0)
+@binding(3)
+var<storage> offsets: array<u32>;
+@group(0)
+@binding(4)
+var<storage, read_write> sorted: array<E>;
+
+const WORKGROUP_SIZE: u32 = 256u;
+const RADIX: u32 = 16u;
+// digit of elements past the end, never matches real digit
+const NO_DIGIT: u32 = 16u;
+
+var<workgroup> counts: array<atomic<u32>, RADIX>;
+// per digit counts packed as 16 bit fields, digits 0..8 in low and 8..16 in high,
+// component c of vector holds digits 2c and 2c + 1
+var<workgroup> packed_low: array<vec4<u32>, WORKGROUP_SIZE>;
+var<workgroup> packed_high: array<vec4<u32>, WORKGROUP_SIZE>;
+
+// one hot flag of digit in packed layout, `NO_DIGIT` has no flag
+fn flag(d: u32, high: bool) -> vec4<u32> {
+    var packed = vec4<u32>(0u);
+    if d < RADIX && (d >= 8u) == high {
+        let field = d % 8u;
+        packed[field / 2u] = 1u << (16u * (field % 2u));
+    }
+    return packed;
+}
+
+fn digit(i: u32) -> u32 {
+    return (key_of(elements[i]) >> params.shift) & (RADIX - 1u);
+}
+
+// counts digits of every block, histogram is digit major,
+// so its exclusive scan is position of first element of digit in block
+@compute
+@workgroup_size(WORKGROUP_SIZE)
+fn count_digits(
+    @builtin(local_invocation_index) local: u32,
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    let block = workgroup_index(workgroup_id, num_workgroups);
+    if local < RADIX {
+        atomicStore(&counts[local], 0u);
+    }
+    workgroupBarrier();
+
+    let i = block * WORKGROUP_SIZE + local;
+    if i < params.n {
+        atomicAdd(&counts[digit(i)], 1u);
+    }
+    workgroupBarrier();
+
+    if local < RADIX && block < params.blocks {
+        histogram[local * params.blocks + block] = atomicLoad(&counts[local]);
+    }
+}
+
+// moves elements to their place, elements of same digit keep their order
+@compute
+@workgroup_size(WORKGROUP_SIZE)
+fn scatter(
+    @builtin(local_invocation_index) local: u32,
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    let block = workgroup_index(workgroup_id, num_workgroups);
+    let i = block * WORKGROUP_SIZE + local;
+
+    var d = NO_DIGIT;
+    if i < params.n {
+        d = digit(i);
+    }
+
+    // inclusive scan of digit flags over block, all digits at once,
+    // counts stay below 2^16 so packed fields never carry into each other
+    var low = flag(d, false);
+    var high = flag(d, true);
+    packed_low[local] = low;
+    packed_high[local] = high;
+    for (var offset = 1u; offset < WORKGROUP_SIZE; offset *= 2u) {
+        workgroupBarrier();
+        if local >= offset {
+            low += packed_low[local - offset];
+            high += packed_high[local - offset];
+        }
+        workgroupBarrier();
+        packed_low[local] = low;
+        packed_high[local] = high;
+    }
+
+    if d == NO_DIGIT {
+        return;
+    }
+
+    // rank among preceding elements of block with same digit,
+    // inclusive count includes element itself
+    let field = d % 8u;
+    var packed = low;
+    if d >= 8u {
+        packed = high;
+    }
+    let rank = ((packed[field / 2u] >> (16u * (field % 2u))) & 0xffffu) - 1u;
+    sorted[offsets[d * params.blocks + block] + rank] = elements[i];
+}
+
+struct Params {
+    n: u32,
+    // bit offset of digit sorted by this pass
+    shift: u32,
+    blocks: u32,
+}
@@ -0,0 +1,63 @@
+// each workgroup swaps pair of TILE × TILE blocks mirrored across diagonal,
+// workgroups below diagonal have nothing to do. Both blocks are staged
+// in workgroup memory before either is written, so matrix is transposed in place
+@group(0)
+@binding(0)
+var<storage, read_write> x: array<T>;
+@group(0)
+@binding(1)
+var<uniform> shape: Shape;
+
+const TILE: u32 = 16u;
+
+// extra column shifts every row of block by one bank
+const PADDED: u32 = TILE + 1u;
+
+var<workgroup> upper: array<array<T, PADDED>, TILE>;
+var<workgroup> lower: array<array<T, PADDED>, TILE>;
+
+@compute
+@workgroup_size(TILE, TILE)
+fn main(
+    @builtin(local_invocation_id) local_id: vec3<u32>,
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    // grid of blocks is repeated for every matrix of batch
+    let n = shape.n;
+    let blocks = (n + TILE - 1u) / TILE;
+    let block = tile_index(workgroup_index(workgroup_id, num_workgroups), blocks, blocks);
+    // block is uniform, so whole workgroup leaves together
+    if block.x < block.y || block.z >= shape.batch {
+        return;
+    }
+    let offset = block.z * n * n;
+
+    // block at (y, x) is above diagonal and its mirror at (x, y) below it
+    let upper_row = block.y * TILE + local_id.y;
+    let upper_col = block.x * TILE + local_id.x;
+    let lower_row = block.x * TILE + local_id.y;
+    let lower_col = block.y * TILE + local_id.x;
+    if upper_row < n && upper_col < n {
+        upper[local_id.y][local_id.x] = x[offset + upper_row * n + upper_col];
+    }
+    if lower_row < n && lower_col < n {
+        lower[local_id.y][local_id.x] = x[offset + lower_row * n + lower_col];
+    }
+
+    workgroupBarrier();
+
+    // transposed lower block takes place of upper one and the other way round,
+    // block on diagonal is its own mirror and both writes store the same values
+    if upper_row < n && upper_col < n {
+        x[offset + upper_row * n + upper_col] = lower[local_id.x][local_id.y];
+    }
+    if lower_row < n && lower_col < n {
+        x[offset + lower_row * n + lower_col] = upper[local_id.x][local_id.y];
+    }
+}
+
+struct Shape {
+    n: u32,
+    batch: u32,
+}
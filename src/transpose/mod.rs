@@ -1 +1,2 @@
+pub mod permute;
 pub mod transpose;
\ No newline at end of file
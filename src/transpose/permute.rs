@@ -0,0 +1,111 @@
+use bytemuck::{Pod, Zeroable};
+
+use crate::{
+    element::{specialise, Element},
+    helpers::with_dispatch,
+    Error, GpuContext,
+};
+
+use super::transpose::execute_transpose;
+
+// has to match WORKGROUP_SIZE in shader
+const WORKGROUP_SIZE: u32 = 256;
+
+/// most axes `permute` handles
+pub const MAX_DIMS: usize = 6;
+
+#[repr(C)]
+#[derive(Clone, Copy, Zeroable, Pod)]
+struct PermuteShape {
+    len: u32,
+    dims: u32,
+    _pad: [u32; 2],
+    extents: [u32; 8],
+    strides: [u32; 8],
+}
+
+/// permute
+/// Reorders axes of tensor of given `shape` stored in row major order, axis `i` of result
+/// is axis `axes[i]` of `data`, so result has shape `axes.map(|axis| shape[axis])`.
+/// `permute(data, [rows, cols], [1, 0])` is transpose, `[0, 2, 3, 1]` turns NCHW into NHWC.
+/// Up to 6 axes are supported
+pub async fn permute<T: Element>(
+    ctx: &GpuContext,
+    data: &[T],
+    shape: &[usize],
+    axes: &[usize],
+) -> Result<Vec<T>, Error> {
+    check_axes(shape, axes)?;
+    let len = shape.iter().product();
+    if data.len() != len {
+        return Err(Error::LengthMismatch {
+            expected: len,
+            found: data.len(),
+        });
+    }
+    if len == 0 {
+        return Ok(Vec::new());
+    }
+
+    // row major strides of data
+    let mut strides = [0; MAX_DIMS];
+    let mut stride = 1;
+    for axis in (0..shape.len()).rev() {
+        strides[axis] = stride;
+        stride *= shape[axis];
+    }
+
+    let mut params = PermuteShape {
+        len: len as u32,
+        dims: axes.len() as u32,
+        _pad: [0; 2],
+        extents: [0; 8],
+        strides: [0; 8],
+    };
+    for (idx, &axis) in axes.iter().enumerate() {
+        params.extents[idx] = shape[axis] as u32;
+        params.strides[idx] = strides[axis] as u32;
+    }
+
+    // creation of compute pipeline with entrypoint "main"
+    let compute_pipeline = ctx.pipeline(
+        &specialise::<T>(&with_dispatch(include_str!("permute.wgsl"))),
+        "main",
+    );
+
+    execute_transpose(
+        ctx,
+        &compute_pipeline,
+        data,
+        params,
+        (len as u32).div_ceil(WORKGROUP_SIZE),
+    )
+    .await
+}
+
+// axes have to name every axis of shape exactly once
+fn check_axes(shape: &[usize], axes: &[usize]) -> Result<(), Error> {
+    if shape.len() > MAX_DIMS {
+        return Err(Error::InvalidInput(format!(
+            "permute handles up to {MAX_DIMS} axes, found {}",
+            shape.len()
+        )));
+    }
+    if axes.len() != shape.len() {
+        return Err(Error::LengthMismatch {
+            expected: shape.len(),
+            found: axes.len(),
+        });
+    }
+    let mut seen = [false; MAX_DIMS];
+    for &axis in axes {
+        if axis >= shape.len() || seen[axis] {
+            return Err(Error::InvalidInput(format!(
+                "axes {axes:?} are not permutation of {} axes",
+                shape.len()
+            )));
+        }
+        seen[axis] = true;
+    }
+    Ok(())
+}
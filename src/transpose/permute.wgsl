@@ -0,0 +1,48 @@
+// each invocation writes one element of out, which is gathered from x
+@group(0)
+@binding(0)
+var<storage> x: array<T>;
+@group(0)
+@binding(1)
+var<storage, read_write> out: array<T>;
+@group(0)
+@binding(2)
+var<uniform> shape: Shape;
+
+const WORKGROUP_SIZE: u32 = 256u;
+
+// offset in x of element at index `i` of out
+fn source(i: u32) -> u32 {
+    var rest = i;
+    var offset = 0u;
+    // innermost axis of out changes fastest
+    for (var axis = shape.dims; axis > 0u; axis -= 1u) {
+        let d = axis - 1u;
+        let extent = shape.extents[d / 4u][d % 4u];
+        offset += (rest % extent) * shape.strides[d / 4u][d % 4u];
+        rest /= extent;
+    }
+    return offset;
+}
+
+@compute
+@workgroup_size(WORKGROUP_SIZE)
+fn main(
+    @builtin(local_invocation_index) local: u32,
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    let i = workgroup_index(workgroup_id, num_workgroups) * WORKGROUP_SIZE + local;
+    if i < shape.len {
+        out[i] = x[source(i)];
+    }
+}
+
+// extents are dimensions of out, strides are strides of x
+// along the same axes, both packed four per vector
+struct Shape {
+    len: u32,
+    dims: u32,
+    extents: array<vec4<u32>, 2>,
+    strides: array<vec4<u32>, 2>,
+}
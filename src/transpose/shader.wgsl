@@ -1,16 +1,41 @@
 @group(0)
 @binding(0)
-var<storage> x: array<i32>; 
+var<storage> x: array<T>;
 @group(0)
 @binding(1)
-var<storage, read_write> out: array<i32>;
+var<storage, read_write> out: array<T>;
+@group(0)
+@binding(2)
+var<uniform> shape: Shape;
 
-fn transpose(x_cord: u32, y_cord: u32) {
-    out[y_cord * 4 + x_cord] = x[x_cord * 4 + y_cord];
+fn transpose(offset: u32, row: u32, col: u32) {
+    out[offset + col * shape.rows + row] = x[offset + row * shape.cols + col];
 }
 
+const WORKGROUP_SIZE: u32 = 8u;
+
 @compute
-@workgroup_size(1)
-fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
-    transpose(global_id.x, global_id.y);
-}
\ No newline at end of file
+@workgroup_size(WORKGROUP_SIZE, WORKGROUP_SIZE)
+fn main(
+    @builtin(local_invocation_id) local_id: vec3<u32>,
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    // grid of tiles of x is repeated for every matrix of batch
+    let tiles_x = (shape.cols + WORKGROUP_SIZE - 1u) / WORKGROUP_SIZE;
+    let tiles_y = (shape.rows + WORKGROUP_SIZE - 1u) / WORKGROUP_SIZE;
+    let tile = tile_index(workgroup_index(workgroup_id, num_workgroups), tiles_x, tiles_y);
+    let row = tile.y * WORKGROUP_SIZE + local_id.y;
+    let col = tile.x * WORKGROUP_SIZE + local_id.x;
+    // dispatch is rounded up to whole workgroups
+    if tile.z < shape.batch && row < shape.rows && col < shape.cols {
+        transpose(tile.z * shape.rows * shape.cols, row, col);
+    }
+}
+
+// x is rows × cols and out is cols × rows
+struct Shape {
+    rows: u32,
+    cols: u32,
+    batch: u32,
+}
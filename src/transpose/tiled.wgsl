@@ -0,0 +1,64 @@
+// T alias and TILE constant are prepended when pipeline is created,
+// each workgroup transposes TILE × TILE block of x through workgroup memory
+// so that both reads of x and writes of out are coalesced
+@group(0)
+@binding(0)
+var<storage> x: array<T>;
+@group(0)
+@binding(1)
+var<storage, read_write> out: array<T>;
+@group(0)
+@binding(2)
+var<uniform> shape: Shape;
+
+// rows of block handled by one step of invocations
+const ROWS: u32 = 8u;
+
+// extra column shifts every row of block by one bank,
+// so reading column of block doesn't hit the same bank TILE times
+const PADDED: u32 = TILE + 1u;
+
+var<workgroup> block: array<array<T, PADDED>, TILE>;
+
+@compute
+@workgroup_size(TILE, ROWS)
+fn main(
+    @builtin(local_invocation_id) local_id: vec3<u32>,
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    let tiles_x = (shape.cols + TILE - 1u) / TILE;
+    let tiles_y = (shape.rows + TILE - 1u) / TILE;
+    let block_index = tile_index(workgroup_index(workgroup_id, num_workgroups), tiles_x, tiles_y);
+    // whole workgroup of rounded up dispatch leaves together, so barrier below stays uniform
+    if block_index.z > 0u {
+        return;
+    }
+
+    // neighbouring invocations read neighbouring columns of x
+    let col = block_index.x * TILE + local_id.x;
+    for (var step = 0u; step < TILE; step += ROWS) {
+        let row = block_index.y * TILE + local_id.y + step;
+        if row < shape.rows && col < shape.cols {
+            block[local_id.y + step][local_id.x] = x[row * shape.cols + col];
+        }
+    }
+
+    workgroupBarrier();
+
+    // neighbouring invocations write neighbouring columns of out,
+    // which are read from column of block
+    let out_col = block_index.y * TILE + local_id.x;
+    for (var step = 0u; step < TILE; step += ROWS) {
+        let out_row = block_index.x * TILE + local_id.y + step;
+        if out_row < shape.cols && out_col < shape.rows {
+            out[out_row * shape.rows + out_col] = block[local_id.x][local_id.y + step];
+        }
+    }
+}
+
+// x is rows × cols and out is cols × rows
+struct Shape {
+    rows: u32,
+    cols: u32,
+}
@@ -1,49 +1,125 @@
-use wgpu::{BufferUsages, Device, Queue};
+use bytemuck::{Pod, Zeroable};
+use wgpu::BufferUsages;
 
 use crate::{
-    helpers::{
-        create_bind_group, create_pipeline, create_staging_buffer, create_storage_buffer,
-    },
-    Error,
+    buffer::GpuBuffer,
+    element::{specialise, Element},
+    helpers::{create_bind_group, dispatch_size, with_dispatch},
+    Error, GpuContext,
 };
 
-// executes shader with given parameters
-async fn execute_shader(x: &[i32], device: &Device, queue: &Queue) -> Result<Vec<i32>, Error> {
-    let out = vec![0; x.len()];
-    let out_slice = out.as_slice();
-    let size = size_of_val(out_slice) as wgpu::BufferAddress;
-    // return buffer
-    // MAP_READ allows for reading it
-    // COPY_DST allows for it to be desetination of cpy
-    let staging_buffer_out = create_staging_buffer(device, size);
+// has to match workgroup_size in shader
+const WORKGROUP_SIZE: u32 = 8;
+// has to match ROWS in tiled shader
+const TILED_ROWS: u32 = 8;
+// has to match TILE in in place shader
+const IN_PLACE_TILE: u32 = 16;
+
+#[repr(C)]
+#[derive(Clone, Copy, Zeroable, Pod)]
+struct TransposeShape {
+    rows: u32,
+    cols: u32,
+    batch: u32,
+    _pad: u32,
+}
 
-    // output buffer that is avaliable for GPU
-    let storage_buffer_out = create_storage_buffer(
-        device,
-        x,
-        BufferUsages::STORAGE | BufferUsages::COPY_DST | BufferUsages::COPY_SRC,
-    );
+impl TransposeShape {
+    fn new(rows: usize, cols: usize, batch: usize) -> Self {
+        Self {
+            rows: rows as u32,
+            cols: cols as u32,
+            batch: batch as u32,
+            _pad: 0,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Zeroable, Pod)]
+struct InPlaceShape {
+    n: u32,
+    batch: u32,
+    _pad: [u32; 2],
+}
+
+/// transpose
+/// Transposes `rows`×`cols` matrix stored in row major order, result is `cols`×`rows`
+pub async fn transpose<T: Element>(
+    ctx: &GpuContext,
+    x: &[T],
+    rows: usize,
+    cols: usize,
+) -> Result<Vec<T>, Error> {
+    transpose_batched(ctx, x, 1, rows, cols).await
+}
+
+/// batched transpose
+/// Transposes `batch` matrices of `rows`×`cols` stored back to back in single dispatch,
+/// every matrix of result is `cols`×`rows`
+pub async fn transpose_batched<T: Element>(
+    ctx: &GpuContext,
+    x: &[T],
+    batch: usize,
+    rows: usize,
+    cols: usize,
+) -> Result<Vec<T>, Error> {
+    check_shape(x.len(), batch * rows, cols)?;
+    if x.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // creation of compute pipeline with entrypoint "main"
+    let source = specialise::<T>(&with_dispatch(include_str!("shader.wgsl")));
+    let compute_pipeline = ctx.pipeline(&source, "main");
+
+    // grid of tiles per matrix of batch
+    let tiles = (cols as u32).div_ceil(WORKGROUP_SIZE) * (rows as u32).div_ceil(WORKGROUP_SIZE);
+    let shape = TransposeShape::new(rows, cols, batch);
+    execute_transpose(ctx, &compute_pipeline, x, shape, tiles * batch as u32).await
+}
+
+/// in place transpose
+/// Transposes `batch` square `n`×`n` matrices stored back to back in `x` without
+/// allocating output, blocks mirrored across diagonal are swapped through workgroup memory
+pub fn transpose_in_place<T: Element>(
+    ctx: &GpuContext,
+    x: &GpuBuffer<T>,
+    batch: usize,
+    n: usize,
+) -> Result<(), Error> {
+    x.check_storage()?;
+    check_shape(x.len(), batch * n, n)?;
+    if x.is_empty() {
+        return Ok(());
+    }
 
     // buffer that is avaliable for GPU
-    let storage_buffer_x = create_storage_buffer(device, x, BufferUsages::STORAGE);
+    let shape = InPlaceShape {
+        n: n as u32,
+        batch: batch as u32,
+        _pad: [0; 2],
+    };
+    let uniform_buffer_shape = GpuBuffer::from_slice(ctx, &[shape], BufferUsages::UNIFORM)?;
 
     // creation of compute pipeline with entrypoint "main"
-    let compute_pipeline = create_pipeline(device, include_str!("shader.wgsl"), "main");
+    let compute_pipeline = ctx.pipeline(
+        &specialise::<T>(&with_dispatch(include_str!("in_place.wgsl"))),
+        "main",
+    );
 
     // binding buffer to group zero with specific bindings
     let bind_group = create_bind_group(
-        device,
+        &ctx.device,
         &compute_pipeline,
-        [
-            (0, storage_buffer_x.as_entire_binding()),
-            (1, storage_buffer_out.as_entire_binding()),
-        ],
+        [(0, x.as_binding()), (1, uniform_buffer_shape.as_binding())],
     );
 
     // creates command encoder
     // its role is to execute pipelines (one ore more)
-    let mut encoder =
-        device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
 
     // compute pass is invoked in other scope,
     // it needs to be dealocated before we can use encoder again
@@ -54,50 +130,123 @@ async fn execute_shader(x: &[i32], device: &Device, queue: &Queue) -> Result<Vec
         });
         cpass.set_pipeline(&compute_pipeline);
         cpass.set_bind_group(0, &bind_group, &[]);
-        cpass.insert_debug_marker("transposition");
-        cpass.dispatch_workgroups(x.len() as u32 / 4, x.len() as u32 / 4, 1);
+        cpass.insert_debug_marker("in place transposition");
+        // workgroup per pair of blocks of every matrix, the ones below diagonal
+        // return right away
+        let blocks = (n as u32).div_ceil(IN_PLACE_TILE);
+        let workgroups = dispatch_size(blocks * blocks * batch as u32, &ctx.device.limits());
+        cpass.dispatch_workgroups(workgroups.0, workgroups.1, workgroups.2);
     }
 
-    // copy result
-    encoder.copy_buffer_to_buffer(&storage_buffer_out, 0, &staging_buffer_out, 0, size);
+    ctx.queue.submit(Some(encoder.finish()));
+    Ok(())
+}
 
-    queue.submit(Some(encoder.finish()));
+/// tiled transpose
+/// Same as `transpose`, but `tile`×`tile` blocks are staged in padded workgroup memory,
+/// so both reads and writes of storage are coalesced. `tile` has to be multiple of 8,
+/// 16 and 32 are good choices
+pub async fn transpose_tiled<T: Element>(
+    ctx: &GpuContext,
+    x: &[T],
+    rows: usize,
+    cols: usize,
+    tile: u32,
+) -> Result<Vec<T>, Error> {
+    check_shape(x.len(), rows, cols)?;
+
+    let limits = ctx.device.limits();
+    if tile == 0 || tile % TILED_ROWS != 0 {
+        return Err(Error::InvalidInput(format!(
+            "tile {tile} is not multiple of {TILED_ROWS}"
+        )));
+    }
+    if tile > limits.max_compute_workgroup_size_x
+        || tile * TILED_ROWS > limits.max_compute_invocations_per_workgroup
+        || tile * (tile + 1) * size_of::<T>() as u32 > limits.max_compute_workgroup_storage_size
+    {
+        return Err(Error::InvalidInput(format!(
+            "tile {tile} exceeds device limits"
+        )));
+    }
+    if x.is_empty() {
+        return Ok(Vec::new());
+    }
 
-    // sending data back to host
-    let buffer_slice = staging_buffer_out.slice(..);
+    // creation of compute pipeline with entrypoint "main",
+    // every tile size gets its own cached pipeline
+    let source = specialise::<T>(&with_dispatch(&format!(
+        "const TILE: u32 = {tile}u;\n{}",
+        include_str!("tiled.wgsl")
+    )));
+    let compute_pipeline = ctx.pipeline(&source, "main");
+
+    // one workgroup per block of input
+    let blocks = (cols as u32).div_ceil(tile) * (rows as u32).div_ceil(tile);
+    let shape = TransposeShape::new(rows, cols, 1);
+    execute_transpose(ctx, &compute_pipeline, x, shape, blocks).await
+}
 
-    let (sender, receiver) = flume::bounded(1);
-    buffer_slice.map_async(wgpu::MapMode::Read, move |v| sender.send(v).unwrap());
+fn check_shape(len: usize, rows: usize, cols: usize) -> Result<(), Error> {
+    if len != rows * cols {
+        return Err(Error::LengthMismatch {
+            expected: rows * cols,
+            found: len,
+        });
+    }
+    Ok(())
+}
 
-    // await for GPU processes
-    device.poll(wgpu::Maintain::wait()).panic_on_timeout();
+// runs kernel that rearranges elements of x into output of the same length,
+// transpose and permute kernels share bindings and differ only in shape uniform
+pub(crate) async fn execute_transpose<T: Element, S: Pod>(
+    ctx: &GpuContext,
+    compute_pipeline: &wgpu::ComputePipeline,
+    x: &[T],
+    shape: S,
+    workgroups: u32,
+) -> Result<Vec<T>, Error> {
+    // output buffer that is avaliable for GPU
+    let storage_buffer_out = GpuBuffer::<T>::zeroed(ctx, x.len(), BufferUsages::STORAGE)?;
 
-    // await for buffer
-    if let Ok(Ok(())) = receiver.recv_async().await {
-        let data = buffer_slice.get_mapped_range();
-        let result: Vec<i32> = bytemuck::cast_slice(&data).to_vec();
-        // all veiws have to be dropped manualy
-        drop(data);
-        staging_buffer_out.unmap();
+    // buffers that are avaliable for GPU
+    let storage_buffer_x = GpuBuffer::from_slice(ctx, x, BufferUsages::STORAGE)?;
+    let uniform_buffer_shape = GpuBuffer::from_slice(ctx, &[shape], BufferUsages::UNIFORM)?;
 
-        return Ok(result);
-    }
+    // binding buffer to group zero with specific bindings
+    let bind_group = create_bind_group(
+        &ctx.device,
+        compute_pipeline,
+        [
+            (0, storage_buffer_x.as_binding()),
+            (1, storage_buffer_out.as_binding()),
+            (2, uniform_buffer_shape.as_binding()),
+        ],
+    );
 
-    Err(Error::ExecutionError)
-}
+    // creates command encoder
+    // its role is to execute pipelines (one ore more)
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
 
-pub async fn execute_transpose(device: Device, queue: Queue) -> Result<(), Error> {
-    #[rustfmt::skip]
-    let x = [
-        1,  2,  3,  4, 
-        5,  6,  7,  8,
-        9,  10, 11, 12,
-        13, 14, 15, 16
-    ];
+    // compute pass is invoked in other scope,
+    // it needs to be dealocated before we can use encoder again
+    {
+        let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: None,
+            timestamp_writes: None,
+        });
+        cpass.set_pipeline(compute_pipeline);
+        cpass.set_bind_group(0, &bind_group, &[]);
+        cpass.insert_debug_marker("transposition");
+        // workgroups beyond dispatch limit continue in y and z dimension
+        let workgroups = dispatch_size(workgroups, &ctx.device.limits());
+        cpass.dispatch_workgroups(workgroups.0, workgroups.1, workgroups.2);
+    }
 
-    let result = execute_shader(&x, &device, &queue).await?;
+    ctx.queue.submit(Some(encoder.finish()));
 
-    println!("x: {:?}", x);
-    println!("{:?}", result);
-    Ok(())
+    // copy result back to host
+    storage_buffer_out.download().await
 }
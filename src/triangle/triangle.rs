@@ -1,6 +1,5 @@
 use wgpu::{
-    Device, LoadOp, RequestAdapterOptions, ShaderModuleDescriptor, ShaderSource, StoreOp, Surface,
-    SurfaceConfiguration,
+    Device, LoadOp, ShaderModuleDescriptor, ShaderSource, StoreOp, Surface, SurfaceConfiguration,
 };
 use winit::{
     dpi::PhysicalSize,
@@ -9,9 +8,13 @@ use winit::{
     window::Window,
 };
 
-use crate::Error;
+use crate::{
+    adapter::{create_instance, request_adapter, AdapterOptions},
+    Error,
+};
 
-pub async fn execute_triangle() -> Result<(), Error> {
+/// renders triangle in new window until it is closed
+pub async fn render_triangle(options: &AdapterOptions) -> Result<(), Error> {
     let event_loop = EventLoop::new()?;
     let window = winit::window::WindowBuilder::new().build(&event_loop)?;
 
@@ -19,18 +22,11 @@ pub async fn execute_triangle() -> Result<(), Error> {
     size.width = 1;
     size.height = 1;
 
-    let instance = wgpu::Instance::default();
+    let instance = create_instance(options);
 
     let surface = instance.create_surface(&window)?;
 
-    let adapter = instance
-        .request_adapter(&RequestAdapterOptions {
-            power_preference: wgpu::PowerPreference::HighPerformance,
-            force_fallback_adapter: false,
-            compatible_surface: Some(&surface),
-        })
-        .await
-        .ok_or(Error::AdapterAquasitionError)?;
+    let adapter = request_adapter(&instance, options, Some(&surface)).await?;
 
     let (device, queue) = adapter
         .request_device(
@@ -155,6 +151,6 @@ fn handle_resize(
 ) {
     config.width = new_size.width.max(1);
     config.height = new_size.height.max(1);
-    surface.configure(&device, &config);
+    surface.configure(device, config);
     window.request_redraw();
 }
@@ -0,0 +1,107 @@
+mod common;
+
+use common::context;
+use learning_wgpu::{
+    buffer::GpuBuffer,
+    helpers::{read_buffer, write_buffer},
+    Error,
+};
+use wgpu::{BindingResource, BufferUsages};
+
+fn buffer(data: &[u32]) -> GpuBuffer<u32> {
+    GpuBuffer::from_slice(context(), data, BufferUsages::STORAGE).unwrap()
+}
+
+#[test]
+fn sub_range_download() {
+    let x = buffer(&[1, 2, 3, 4, 5]);
+
+    assert_eq!(smol::block_on(x.slice(1..4).download()).unwrap(), [2, 3, 4]);
+    assert_eq!(smol::block_on(x.slice(..=1).download()).unwrap(), [1, 2]);
+    assert_eq!(smol::block_on(x.slice(4..).download()).unwrap(), [5]);
+    assert!(smol::block_on(x.slice(2..2).download()).unwrap().is_empty());
+}
+
+#[test]
+fn sub_range_upload() {
+    let x = buffer(&[1, 2, 3, 4, 5]);
+
+    x.slice(1..3).upload(&[20, 30]).unwrap();
+    x.slice(4..).upload(&[50]).unwrap();
+    x.slice(0..0).upload(&[]).unwrap();
+
+    assert_eq!(smol::block_on(x.download()).unwrap(), [1, 20, 30, 4, 50]);
+}
+
+#[test]
+fn upload_length_mismatch() {
+    let x = buffer(&[1, 2, 3]);
+
+    let result = x.slice(1..).upload(&[1, 2, 3]);
+
+    assert!(matches!(
+        result,
+        Err(Error::LengthMismatch {
+            expected: 2,
+            found: 3
+        })
+    ));
+}
+
+#[test]
+fn buffer_above_device_limit() {
+    let limits = context().device.limits();
+    let len = limits.max_storage_buffer_binding_size as usize / size_of::<u32>() + 1;
+
+    let result = GpuBuffer::<u32>::zeroed(context(), len, BufferUsages::STORAGE);
+
+    assert!(matches!(result, Err(Error::InvalidInput(_))));
+}
+
+#[test]
+fn empty_slice_binds_single_element() {
+    let x = buffer(&[1, 2, 3, 4, 5]);
+
+    let BindingResource::Buffer(binding) = x.slice(3..3).as_binding() else {
+        panic!("slice binds buffer");
+    };
+
+    assert_eq!(binding.offset, 0);
+    assert_eq!(binding.size.map(|size| size.get()), Some(4));
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn slice_past_end_panics() {
+    buffer(&[1, 2, 3]).slice(2..4);
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+#[allow(clippy::reversed_empty_ranges)]
+fn reversed_slice_panics() {
+    buffer(&[1, 2, 3]).slice(2..1);
+}
+
+#[test]
+fn unaligned_read_fails() {
+    let x = buffer(&[1, 2, 3]);
+
+    let result = smol::block_on(read_buffer::<u8>(
+        &context().device,
+        &context().queue,
+        x.buffer(),
+        1..2,
+    ));
+
+    assert!(matches!(result, Err(Error::InvalidInput(_))));
+}
+
+#[test]
+fn unaligned_write_fails() {
+    let x = buffer(&[1, 2, 3]);
+
+    let result = write_buffer::<u8>(&context().queue, x.buffer(), 2, &[1, 2, 3, 4]);
+
+    assert!(matches!(result, Err(Error::InvalidInput(_))));
+}
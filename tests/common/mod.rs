@@ -0,0 +1,39 @@
+#![allow(dead_code)]
+
+use std::sync::OnceLock;
+
+use learning_wgpu::{AdapterOptions, GpuContext};
+
+/// context shared by all tests of one binary, pipelines are compiled once
+pub fn context() -> &'static GpuContext {
+    static CONTEXT: OnceLock<GpuContext> = OnceLock::new();
+    CONTEXT.get_or_init(|| {
+        smol::block_on(GpuContext::new(&AdapterOptions::default()))
+            .expect("tests need GPU adapter, software fallback is enough")
+    })
+}
+
+/// xorshift generator, tests stay reproducible without extra dependencies
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng(seed.max(1))
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 >> 32) as u32
+    }
+
+    /// value in `-range..=range`
+    pub fn small_i32(&mut self, range: i32) -> i32 {
+        (self.next_u32() % (2 * range as u32 + 1)) as i32 - range
+    }
+
+    pub fn vec_i32(&mut self, len: usize, range: i32) -> Vec<i32> {
+        (0..len).map(|_| self.small_i32(range)).collect()
+    }
+}
@@ -0,0 +1,119 @@
+mod common;
+
+use common::{context, Rng};
+use learning_wgpu::{
+    batched_matmul, helpers::dispatch_size, matmul, outer_product, saxpy, transpose_batched, Error,
+    Matrix,
+};
+use wgpu::Limits;
+
+#[test]
+fn dispatch_size_stays_within_limits() {
+    let limits = Limits {
+        max_compute_workgroups_per_dimension: 100,
+        ..Limits::downlevel_defaults()
+    };
+    for workgroups in [1, 99, 100, 101, 9_999, 10_000, 10_001, 999_999, 1_000_000] {
+        let (x, y, z) = dispatch_size(workgroups, &limits);
+
+        assert!(
+            x <= 100 && y <= 100 && z <= 100,
+            "{workgroups}: {x}×{y}×{z}"
+        );
+        assert!(x * y * z >= workgroups, "{workgroups}: {x}×{y}×{z}");
+        // only the last layer of dispatch may be partially used
+        assert!(x * y * z < workgroups + x * y, "{workgroups}: {x}×{y}×{z}");
+    }
+    assert_eq!(dispatch_size(0, &limits), (0, 0, 0));
+    assert_eq!(dispatch_size(50, &limits), (50, 1, 1));
+    assert_eq!(dispatch_size(250, &limits), (100, 3, 1));
+    assert_eq!(dispatch_size(25_000, &limits), (100, 100, 3));
+}
+
+#[test]
+fn long_vector() {
+    // more workgroups of 64 elements than fit into one dimension
+    let len = 5_000_000;
+    let x: Vec<u32> = (0..len as u32).collect();
+    let y = vec![7u32; len];
+
+    let result = smol::block_on(saxpy(context(), 3, &x, &y)).unwrap();
+
+    assert!(result
+        .iter()
+        .enumerate()
+        .all(|(i, &v)| v == 3 * i as u32 + 7));
+}
+
+#[test]
+fn buffer_above_downlevel_binding_limit() {
+    // 160 MB per buffer, past 128 MiB storage binding of downlevel defaults,
+    // adapters that can't bind that much have to refuse instead of panicking
+    let len = 40_000_000;
+    let x: Vec<u32> = (0..len as u32).collect();
+    let y = vec![7u32; len];
+
+    let result = smol::block_on(saxpy(context(), 3, &x, &y));
+
+    let max_binding = context().device.limits().max_storage_buffer_binding_size as usize;
+    if len * size_of::<u32>() > max_binding {
+        assert!(matches!(result, Err(Error::InvalidInput(_))));
+        return;
+    }
+    assert!(result
+        .unwrap()
+        .iter()
+        .enumerate()
+        .all(|(i, &v)| v == 3u32.wrapping_mul(i as u32).wrapping_add(7)));
+}
+
+#[test]
+fn long_outer_product() {
+    let mut rng = Rng::new(101);
+    let x = rng.vec_i32(70_000, 100);
+    let y = [2, -1];
+
+    let result = smol::block_on(outer_product(context(), &x, &y)).unwrap();
+
+    let expected: Vec<i32> = x.iter().flat_map(|v| [2 * v, -v]).collect();
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn wide_matrix() {
+    // more tiles of 8 columns than fit into one dimension
+    let n = 600_000;
+    let lhs = Matrix::new(vec![3], 1, 1).unwrap();
+    let rhs = Matrix::new((0..n as i32).collect(), 1, n).unwrap();
+
+    let result = smol::block_on(matmul(context(), &lhs, &rhs)).unwrap();
+
+    assert!(result
+        .data()
+        .iter()
+        .enumerate()
+        .all(|(i, &v)| v == 3 * i as i32));
+}
+
+#[test]
+fn large_batches() {
+    let batch = 70_000;
+    let mut rng = Rng::new(102);
+    let data = rng.vec_i32(batch * 2, 100);
+
+    let lhs: Vec<_> = data
+        .chunks(2)
+        .map(|pair| Matrix::new(pair.to_vec(), 1, 2).unwrap())
+        .collect();
+    let rhs = [Matrix::new(vec![1, 10], 2, 1).unwrap()];
+    let products = smol::block_on(batched_matmul(context(), &lhs, &rhs)).unwrap();
+    let expected: Vec<i32> = data.chunks(2).map(|pair| pair[0] + 10 * pair[1]).collect();
+    assert_eq!(
+        products.iter().map(|m| m.data()[0]).collect::<Vec<_>>(),
+        expected
+    );
+
+    // 1×2 matrices become 2×1, which leaves data unchanged
+    let transposed = smol::block_on(transpose_batched(context(), &data, batch, 1, 2)).unwrap();
+    assert_eq!(transposed, data);
+}
@@ -0,0 +1,67 @@
+mod common;
+
+use common::{context, Rng};
+use learning_wgpu::{dot, Error};
+
+#[test]
+fn million_elements() {
+    let mut rng = Rng::new(111);
+    for len in [1, 255, 256, 257, 1_000_000, 1_500_001] {
+        let (x, y) = (rng.vec_i32(len, 10), rng.vec_i32(len, 10));
+        let expected = x
+            .iter()
+            .zip(&y)
+            .fold(0i32, |sum, (a, b)| sum.wrapping_add(a * b));
+
+        let result = smol::block_on(dot(context(), &x, &y)).unwrap();
+
+        assert_eq!(result, expected, "{len} elements");
+    }
+}
+
+#[test]
+fn million_floats() {
+    let mut rng = Rng::new(112);
+    let len = 2_000_000;
+    let x: Vec<f32> = (0..len)
+        .map(|_| rng.small_i32(100) as f32 / 100.0)
+        .collect();
+    let y: Vec<f32> = (0..len)
+        .map(|_| rng.small_i32(100) as f32 / 100.0)
+        .collect();
+    let products: Vec<f64> = x
+        .iter()
+        .zip(&y)
+        .map(|(&a, &b)| a as f64 * b as f64)
+        .collect();
+    let expected: f64 = products.iter().sum();
+    let magnitude: f64 = products.iter().map(|p| p.abs()).sum();
+
+    let result = smol::block_on(dot(context(), &x, &y)).unwrap();
+
+    // partial sums are accumulated in different order than on host
+    assert!(
+        (result as f64 - expected).abs() <= 1e-5 * magnitude,
+        "{result} != {expected}"
+    );
+}
+
+#[test]
+fn empty_vectors() {
+    let result = smol::block_on(dot::<u32>(context(), &[], &[])).unwrap();
+
+    assert_eq!(result, 0);
+}
+
+#[test]
+fn length_mismatch() {
+    let result = smol::block_on(dot(context(), &[1.0f32, 2.0], &[1.0]));
+
+    assert!(matches!(
+        result,
+        Err(Error::LengthMismatch {
+            expected: 2,
+            found: 1
+        })
+    ));
+}
@@ -0,0 +1,80 @@
+mod common;
+
+use common::{context, Rng};
+use learning_wgpu::{gemv, gemv_t, Error, Matrix};
+
+fn cpu_gemv(
+    alpha: i32,
+    a: &Matrix<i32>,
+    transpose: bool,
+    x: &[i32],
+    beta: i32,
+    y: &[i32],
+) -> Vec<i32> {
+    y.iter()
+        .enumerate()
+        .map(|(row, &y)| {
+            let sum: i32 = x
+                .iter()
+                .enumerate()
+                .map(|(i, &x)| if transpose { a.get(i, row) } else { a.get(row, i) } * x)
+                .sum();
+            alpha * sum + beta * y
+        })
+        .collect()
+}
+
+fn check(rows: usize, cols: usize, alpha: i32, beta: i32) {
+    let mut rng = Rng::new((rows * 1000 + cols) as u64);
+    let a = Matrix::new(rng.vec_i32(rows * cols, 9), rows, cols).unwrap();
+
+    let x = rng.vec_i32(cols, 9);
+    let mut y = rng.vec_i32(rows, 9);
+    let expected = cpu_gemv(alpha, &a, false, &x, beta, &y);
+    smol::block_on(gemv(context(), alpha, &a, &x, beta, &mut y)).unwrap();
+    assert_eq!(y, expected, "{rows}×{cols}");
+
+    let x = rng.vec_i32(rows, 9);
+    let mut y = rng.vec_i32(cols, 9);
+    let expected = cpu_gemv(alpha, &a, true, &x, beta, &y);
+    smol::block_on(gemv_t(context(), alpha, &a, &x, beta, &mut y)).unwrap();
+    assert_eq!(y, expected, "{rows}×{cols} transposed");
+}
+
+#[test]
+fn plain_product() {
+    check(13, 29, 1, 0);
+}
+
+#[test]
+fn alpha_and_beta() {
+    check(40, 7, -3, 2);
+}
+
+#[test]
+fn rows_longer_than_workgroup() {
+    check(3, 1000, 2, -1);
+    check(1000, 3, 2, -1);
+}
+
+#[test]
+fn empty() {
+    check(0, 5, 1, 1);
+    check(5, 0, 1, 1);
+}
+
+#[test]
+fn length_mismatch() {
+    let a = Matrix::<i32>::zeros(3, 4);
+    let mut y = vec![0; 3];
+
+    let result = smol::block_on(gemv(context(), 1, &a, &[1, 2, 3], 0, &mut y));
+
+    assert!(matches!(
+        result,
+        Err(Error::LengthMismatch {
+            expected: 4,
+            found: 3
+        })
+    ));
+}
@@ -0,0 +1,164 @@
+mod common;
+
+use common::{context, Rng};
+use learning_wgpu::{buffer::GpuBuffer, level1::level1 as blas, Error};
+use wgpu::BufferUsages;
+
+fn upload<T: learning_wgpu::Element>(data: &[T]) -> GpuBuffer<T> {
+    GpuBuffer::from_slice(context(), data, BufferUsages::STORAGE).unwrap()
+}
+
+fn download<T: learning_wgpu::Element>(buffer: &GpuBuffer<T>) -> Vec<T> {
+    smol::block_on(buffer.download()).unwrap()
+}
+
+#[test]
+fn scal_copy_swap() {
+    let mut rng = Rng::new(51);
+    let (x, y) = (rng.vec_i32(1000, 50), rng.vec_i32(1000, 50));
+    let (buffer_x, buffer_y) = (upload(&x), upload(&y));
+
+    blas::scal(context(), -3, &buffer_x).unwrap();
+    let scaled: Vec<_> = x.iter().map(|v| -3 * v).collect();
+    assert_eq!(download(&buffer_x), scaled);
+
+    blas::swap(context(), &buffer_x, &buffer_y).unwrap();
+    assert_eq!(download(&buffer_x), y);
+    assert_eq!(download(&buffer_y), scaled);
+
+    blas::copy(context(), &buffer_x, &buffer_y).unwrap();
+    assert_eq!(download(&buffer_y), y);
+}
+
+#[test]
+fn axpy_and_dot() {
+    let mut rng = Rng::new(52);
+    let (x, y) = (rng.vec_i32(3000, 50), rng.vec_i32(3000, 50));
+    let (buffer_x, buffer_y) = (upload(&x), upload(&y));
+
+    let dot = smol::block_on(blas::dot(context(), &buffer_x, &buffer_y)).unwrap();
+    assert_eq!(dot, x.iter().zip(&y).map(|(a, b)| a * b).sum::<i32>());
+
+    blas::axpy(context(), 5, &buffer_x, &buffer_y).unwrap();
+    let expected: Vec<_> = x.iter().zip(&y).map(|(a, b)| 5 * a + b).collect();
+    assert_eq!(download(&buffer_y), expected);
+}
+
+#[test]
+fn rot() {
+    let x = [1.0f32, 0.0, -2.0, 3.5];
+    let y = [0.0f32, 1.0, 4.0, -0.5];
+    let (buffer_x, buffer_y) = (upload(&x), upload(&y));
+    let (c, s) = (0.6f32, 0.8f32);
+
+    blas::rot(context(), &buffer_x, &buffer_y, c, s).unwrap();
+
+    let rotated_x: Vec<_> = x.iter().zip(&y).map(|(a, b)| c * a + s * b).collect();
+    let rotated_y: Vec<_> = x.iter().zip(&y).map(|(a, b)| c * b - s * a).collect();
+    assert_eq!(download(&buffer_x), rotated_x);
+    assert_eq!(download(&buffer_y), rotated_y);
+}
+
+#[test]
+fn asum_and_nrm2() {
+    let mut rng = Rng::new(53);
+    // spans several reduction passes
+    let x = rng.vec_i32(300_000, 50);
+
+    let asum = smol::block_on(blas::asum(context(), &upload(&x))).unwrap();
+    assert_eq!(asum, x.iter().map(|v| v.abs()).sum::<i32>());
+
+    let floats: Vec<f32> = x.iter().map(|&v| v as f32 / 8.0).collect();
+    let nrm2 = smol::block_on(blas::nrm2(context(), &upload(&floats))).unwrap();
+    let expected = floats.iter().map(|v| (v * v) as f64).sum::<f64>().sqrt() as f32;
+    assert!(
+        (nrm2 - expected).abs() <= expected * 1e-5,
+        "{nrm2} != {expected}"
+    );
+}
+
+#[test]
+fn nrm2_does_not_overflow_or_underflow() {
+    let len = 1000;
+    for magnitude in [1e30f32, 1e-30] {
+        let x: Vec<f32> = (0..len).map(|i| (i % 7) as f32 * magnitude).collect();
+        let expected = x.iter().map(|&v| (v as f64).powi(2)).sum::<f64>().sqrt() as f32;
+
+        let nrm2 = smol::block_on(blas::nrm2(context(), &upload(&x))).unwrap();
+
+        assert!(
+            (nrm2 - expected).abs() <= expected * 1e-5,
+            "{nrm2} != {expected}"
+        );
+    }
+
+    let zeros = smol::block_on(blas::nrm2(context(), &upload(&[0.0; 5]))).unwrap();
+    assert_eq!(zeros, 0.0);
+    let infinite = [1.0, f32::INFINITY, 2.0];
+    let nrm2 = smol::block_on(blas::nrm2(context(), &upload(&infinite))).unwrap();
+    assert_eq!(nrm2, f32::INFINITY);
+}
+
+#[test]
+fn iamax_returns_first_maximum() {
+    let mut rng = Rng::new(54);
+    let mut x = rng.vec_i32(200_000, 50);
+    x[123_456] = -77;
+    x[150_000] = 77;
+
+    let index = smol::block_on(blas::iamax(context(), &upload(&x))).unwrap();
+    assert_eq!(index, Some(123_456));
+
+    let unsigned = [3u32, 9, 1, 9];
+    let index = smol::block_on(blas::iamax(context(), &upload(&unsigned))).unwrap();
+    assert_eq!(index, Some(1));
+
+    let index = smol::block_on(blas::iamax(context(), &upload::<f32>(&[]))).unwrap();
+    assert_eq!(index, None);
+}
+
+#[test]
+fn copy_and_swap_onto_itself() {
+    let x = upload(&[1, 2, 3]);
+
+    blas::copy(context(), &x, &x).unwrap();
+    blas::swap(context(), &x, &x).unwrap();
+
+    assert_eq!(download(&x), [1, 2, 3]);
+}
+
+#[test]
+fn length_mismatch() {
+    let (x, y) = (upload(&[1, 2, 3]), upload(&[1, 2]));
+
+    assert!(matches!(
+        blas::swap(context(), &x, &y),
+        Err(Error::LengthMismatch {
+            expected: 3,
+            found: 2
+        })
+    ));
+    assert!(matches!(
+        smol::block_on(blas::dot(context(), &x, &y)),
+        Err(Error::LengthMismatch { .. })
+    ));
+}
+
+#[test]
+fn buffers_without_storage_usage() {
+    let x = GpuBuffer::from_slice(context(), &[1.0f32, 2.0], BufferUsages::UNIFORM).unwrap();
+    let y = upload(&[3.0f32, 4.0]);
+
+    assert!(matches!(
+        blas::scal(context(), 2.0, &x),
+        Err(Error::InvalidInput(_))
+    ));
+    assert!(matches!(
+        blas::axpy(context(), 2.0, &y, &x),
+        Err(Error::InvalidInput(_))
+    ));
+    assert!(matches!(
+        smol::block_on(blas::nrm2(context(), &x)),
+        Err(Error::InvalidInput(_))
+    ));
+}
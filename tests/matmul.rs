@@ -0,0 +1,314 @@
+mod common;
+
+use common::{context, Rng};
+use learning_wgpu::{batched_matmul, gemm, matmul, matmul_tiled, Error, Matrix, Op, TileConfig};
+
+fn cpu_matmul(lhs: &Matrix<i32>, rhs: &Matrix<i32>) -> Matrix<i32> {
+    let (m, k, n) = (lhs.rows(), lhs.cols(), rhs.cols());
+    let mut out = vec![0; m * n];
+    for row in 0..m {
+        for col in 0..n {
+            out[row * n + col] = (0..k).map(|i| lhs.get(row, i) * rhs.get(i, col)).sum();
+        }
+    }
+    Matrix::new(out, m, n).unwrap()
+}
+
+fn check(m: usize, k: usize, n: usize) {
+    let mut rng = Rng::new((m * 10_000 + k * 100 + n) as u64);
+    let lhs = Matrix::new(rng.vec_i32(m * k, 9), m, k).unwrap();
+    let rhs = Matrix::new(rng.vec_i32(k * n, 9), k, n).unwrap();
+
+    let expected = cpu_matmul(&lhs, &rhs);
+
+    let result = smol::block_on(matmul(context(), &lhs, &rhs)).unwrap();
+    assert_eq!(result, expected, "{m}×{k} by {k}×{n}");
+
+    for (tile, work_per_thread) in [(8, 1), (16, 4), (32, 8)] {
+        let config = TileConfig {
+            tile,
+            work_per_thread,
+        };
+        let result = smol::block_on(matmul_tiled(context(), &lhs, &rhs, config)).unwrap();
+        assert_eq!(result, expected, "{m}×{k} by {k}×{n} with {config:?}");
+    }
+}
+
+#[test]
+fn square() {
+    check(16, 16, 16);
+}
+
+#[test]
+fn row_vector_by_matrix() {
+    check(1, 37, 5);
+    check(1, 64, 1);
+}
+
+#[test]
+fn matrix_by_column_vector() {
+    check(23, 8, 1);
+}
+
+#[test]
+fn prime_sizes() {
+    check(7, 13, 11);
+    check(31, 17, 29);
+    check(2, 3, 5);
+}
+
+#[test]
+fn larger_than_old_fixed_size() {
+    check(100, 45, 70);
+}
+
+#[test]
+fn empty() {
+    check(0, 4, 3);
+    check(3, 0, 2);
+}
+
+#[test]
+fn inner_dimension_mismatch() {
+    let lhs = Matrix::<i32>::zeros(2, 3);
+    let rhs = Matrix::<i32>::zeros(4, 2);
+
+    let result = smol::block_on(matmul(context(), &lhs, &rhs));
+
+    assert!(matches!(
+        result,
+        Err(Error::ShapeMismatch {
+            lhs: (2, 3),
+            rhs: (4, 2)
+        })
+    ));
+}
+
+#[test]
+fn invalid_tile_config() {
+    let lhs = Matrix::<i32>::zeros(2, 2);
+    let config = TileConfig {
+        tile: 16,
+        work_per_thread: 3,
+    };
+
+    let result = smol::block_on(matmul_tiled(context(), &lhs, &lhs, config));
+
+    assert!(matches!(result, Err(Error::InvalidInput(_))));
+}
+
+fn op_get(matrix: &Matrix<i32>, op: Op, row: usize, col: usize) -> i32 {
+    match op {
+        Op::Identity => matrix.get(row, col),
+        Op::Transpose => matrix.get(col, row),
+    }
+}
+
+fn cpu_gemm(
+    alpha: i32,
+    a: &Matrix<i32>,
+    op_a: Op,
+    b: &Matrix<i32>,
+    op_b: Op,
+    beta: i32,
+    c: &Matrix<i32>,
+) -> Matrix<i32> {
+    let (m, n) = (c.rows(), c.cols());
+    let k = match op_a {
+        Op::Identity => a.cols(),
+        Op::Transpose => a.rows(),
+    };
+    let mut out = vec![0; m * n];
+    for row in 0..m {
+        for col in 0..n {
+            let sum: i32 = (0..k)
+                .map(|i| op_get(a, op_a, row, i) * op_get(b, op_b, i, col))
+                .sum();
+            out[row * n + col] = alpha * sum + beta * c.get(row, col);
+        }
+    }
+    Matrix::new(out, m, n).unwrap()
+}
+
+fn stored_shape(op: Op, rows: usize, cols: usize) -> (usize, usize) {
+    match op {
+        Op::Identity => (rows, cols),
+        Op::Transpose => (cols, rows),
+    }
+}
+
+#[test]
+fn gemm_all_transpositions() {
+    let mut rng = Rng::new(11);
+    let (m, k, n) = (7, 5, 3);
+    for op_a in [Op::Identity, Op::Transpose] {
+        for op_b in [Op::Identity, Op::Transpose] {
+            let (a_rows, a_cols) = stored_shape(op_a, m, k);
+            let (b_rows, b_cols) = stored_shape(op_b, k, n);
+            let a = Matrix::new(rng.vec_i32(m * k, 9), a_rows, a_cols).unwrap();
+            let b = Matrix::new(rng.vec_i32(k * n, 9), b_rows, b_cols).unwrap();
+            let mut c = Matrix::new(rng.vec_i32(m * n, 9), m, n).unwrap();
+            let expected = cpu_gemm(3, &a, op_a, &b, op_b, -2, &c);
+
+            smol::block_on(gemm(context(), 3, &a, op_a, &b, op_b, -2, &mut c)).unwrap();
+
+            assert_eq!(c, expected, "{op_a:?} {op_b:?}");
+        }
+    }
+}
+
+#[test]
+fn gemm_beta_zero_overwrites_c() {
+    let mut rng = Rng::new(12);
+    let a = Matrix::new(rng.vec_i32(4 * 6, 9), 4, 6).unwrap();
+    let b = Matrix::new(rng.vec_i32(6 * 2, 9), 6, 2).unwrap();
+    let mut c = Matrix::new(vec![i32::MAX; 8], 4, 2).unwrap();
+    let expected = cpu_matmul(&a, &b);
+
+    smol::block_on(gemm(
+        context(),
+        1,
+        &a,
+        Op::Identity,
+        &b,
+        Op::Identity,
+        0,
+        &mut c,
+    ))
+    .unwrap();
+
+    assert_eq!(c, expected);
+}
+
+#[test]
+fn gemm_shape_mismatch() {
+    let a = Matrix::<i32>::zeros(3, 4);
+    let mut c = Matrix::<i32>::zeros(3, 3);
+
+    let inner = smol::block_on(gemm(
+        context(),
+        1,
+        &a,
+        Op::Identity,
+        &a,
+        Op::Identity,
+        0,
+        &mut c,
+    ));
+    let output = smol::block_on(gemm(
+        context(),
+        1,
+        &a,
+        Op::Transpose,
+        &a,
+        Op::Identity,
+        0,
+        &mut c,
+    ));
+
+    assert!(matches!(inner, Err(Error::ShapeMismatch { .. })));
+    assert!(matches!(
+        output,
+        Err(Error::OutputShapeMismatch {
+            expected: (4, 4),
+            found: (3, 3)
+        })
+    ));
+}
+
+fn random_batch(rng: &mut Rng, batch: usize, rows: usize, cols: usize) -> Vec<Matrix<i32>> {
+    (0..batch)
+        .map(|_| Matrix::new(rng.vec_i32(rows * cols, 9), rows, cols).unwrap())
+        .collect()
+}
+
+#[test]
+fn batched_matches_per_pair_matmul() {
+    let mut rng = Rng::new(21);
+    let lhs = random_batch(&mut rng, 37, 5, 9);
+    let rhs = random_batch(&mut rng, 37, 9, 11);
+
+    let result = smol::block_on(batched_matmul(context(), &lhs, &rhs)).unwrap();
+
+    let expected: Vec<_> = lhs
+        .iter()
+        .zip(&rhs)
+        .map(|(x, y)| cpu_matmul(x, y))
+        .collect();
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn batched_broadcasts_single_operand() {
+    let mut rng = Rng::new(22);
+    let single = random_batch(&mut rng, 1, 6, 6);
+    let batch = random_batch(&mut rng, 5, 6, 6);
+
+    let left = smol::block_on(batched_matmul(context(), &single, &batch)).unwrap();
+    let right = smol::block_on(batched_matmul(context(), &batch, &single)).unwrap();
+
+    for (idx, matrix) in batch.iter().enumerate() {
+        assert_eq!(left[idx], cpu_matmul(&single[0], matrix));
+        assert_eq!(right[idx], cpu_matmul(matrix, &single[0]));
+    }
+}
+
+#[test]
+fn batched_shape_errors() {
+    let mut rng = Rng::new(23);
+    let lhs = random_batch(&mut rng, 3, 2, 3);
+    let rhs = random_batch(&mut rng, 2, 3, 2);
+    let wrong_inner = random_batch(&mut rng, 3, 4, 2);
+
+    let batch = smol::block_on(batched_matmul(context(), &lhs, &rhs));
+    let inner = smol::block_on(batched_matmul(context(), &lhs, &wrong_inner));
+
+    assert!(matches!(
+        batch,
+        Err(Error::BatchMismatch { lhs: 3, rhs: 2 })
+    ));
+    assert!(matches!(inner, Err(Error::ShapeMismatch { .. })));
+}
+
+fn cast<T, U>(matrix: &Matrix<T>, f: impl Fn(T) -> U) -> Matrix<U>
+where
+    T: bytemuck::Pod,
+    U: bytemuck::Pod,
+{
+    let data = matrix.data().iter().map(|&value| f(value)).collect();
+    Matrix::new(data, matrix.rows(), matrix.cols()).unwrap()
+}
+
+#[test]
+fn float_and_unsigned_elements() {
+    let mut rng = Rng::new(31);
+    let lhs = Matrix::new(rng.vec_i32(9 * 14, 9), 9, 14).unwrap();
+    let rhs = Matrix::new(rng.vec_i32(14 * 6, 9), 14, 6).unwrap();
+    let expected = cpu_matmul(&lhs, &rhs);
+
+    // small integers are exact in f32, wrapping u32 arithmetic matches i32 bit for bit
+    let (lhs_f32, rhs_f32) = (cast(&lhs, |v| v as f32), cast(&rhs, |v| v as f32));
+    let (lhs_u32, rhs_u32) = (cast(&lhs, |v| v as u32), cast(&rhs, |v| v as u32));
+
+    let result = smol::block_on(matmul(context(), &lhs_f32, &rhs_f32)).unwrap();
+    assert_eq!(result, cast(&expected, |v| v as f32));
+    let config = TileConfig::default();
+    let result = smol::block_on(matmul_tiled(context(), &lhs_f32, &rhs_f32, config)).unwrap();
+    assert_eq!(result, cast(&expected, |v| v as f32));
+    let result = smol::block_on(matmul(context(), &lhs_u32, &rhs_u32)).unwrap();
+    assert_eq!(result, cast(&expected, |v| v as u32));
+
+    let mut c = Matrix::new(vec![1.5; 9 * 6], 9, 6).unwrap();
+    smol::block_on(gemm(
+        context(),
+        0.5,
+        &lhs_f32,
+        Op::Identity,
+        &rhs_f32,
+        Op::Identity,
+        2.0,
+        &mut c,
+    ))
+    .unwrap();
+    assert_eq!(c, cast(&expected, |v| v as f32 * 0.5 + 3.0));
+}
@@ -0,0 +1,118 @@
+mod common;
+
+use common::{context, Rng};
+use learning_wgpu::{arg_reduce, buffer::GpuBuffer, reduce, ArgReduceOp, Element, Error, ReduceOp};
+use wgpu::BufferUsages;
+
+fn upload<T: Element>(data: &[T]) -> GpuBuffer<T> {
+    GpuBuffer::from_slice(context(), data, BufferUsages::STORAGE).unwrap()
+}
+
+fn first_index(data: &[i32], value: i32) -> Option<usize> {
+    data.iter().position(|&v| v == value)
+}
+
+fn check(len: usize) {
+    let mut rng = Rng::new(len as u64 + 61);
+    let data = rng.vec_i32(len, 1000);
+    let buffer = upload(&data);
+    let run = |op| smol::block_on(reduce(context(), op, &buffer)).unwrap();
+    let run_arg = |op| smol::block_on(arg_reduce(context(), op, &buffer)).unwrap();
+
+    let wrapping_sum = data.iter().fold(0i32, |acc, &v| acc.wrapping_add(v));
+    let wrapping_product = data.iter().fold(1i32, |acc, &v| acc.wrapping_mul(v | 1));
+    assert_eq!(
+        run(ReduceOp::Sum),
+        (len > 0).then_some(wrapping_sum),
+        "sum of {len}"
+    );
+    assert_eq!(
+        run(ReduceOp::Min),
+        data.iter().min().copied(),
+        "min of {len}"
+    );
+    assert_eq!(
+        run(ReduceOp::Max),
+        data.iter().max().copied(),
+        "max of {len}"
+    );
+
+    let min = data.iter().min().copied().unwrap_or_default();
+    let max = data.iter().max().copied().unwrap_or_default();
+    assert_eq!(
+        run_arg(ArgReduceOp::Min),
+        first_index(&data, min),
+        "argmin of {len}"
+    );
+    assert_eq!(
+        run_arg(ArgReduceOp::Max),
+        first_index(&data, max),
+        "argmax of {len}"
+    );
+
+    // odd factors keep product from collapsing to zero
+    let odd: Vec<i32> = data.iter().map(|&v| v | 1).collect();
+    let product = smol::block_on(reduce(context(), ReduceOp::Product, &upload(&odd))).unwrap();
+    assert_eq!(
+        product,
+        (len > 0).then_some(wrapping_product),
+        "product of {len}"
+    );
+}
+
+#[test]
+fn lengths_around_workgroup_size() {
+    for len in [0, 1, 2, 255, 256, 257, 1000, 65_537] {
+        check(len);
+    }
+}
+
+#[test]
+fn multiple_passes() {
+    check(3_000_000);
+}
+
+#[test]
+fn floats_and_unsigned() {
+    let floats = [2.5f32, -7.25, 3.0, -7.25, 0.5];
+    let buffer = upload(&floats);
+    let run = |op| smol::block_on(reduce(context(), op, &buffer)).unwrap();
+
+    assert_eq!(run(ReduceOp::Sum), Some(-8.5));
+    assert_eq!(
+        run(ReduceOp::Product),
+        Some(2.5 * -7.25 * 3.0 * -7.25 * 0.5)
+    );
+    assert_eq!(run(ReduceOp::Min), Some(-7.25));
+    let argmin = smol::block_on(arg_reduce(context(), ArgReduceOp::Min, &buffer)).unwrap();
+    assert_eq!(argmin, Some(1));
+
+    let unsigned = [4u32, u32::MAX, 0, u32::MAX];
+    let buffer = upload(&unsigned);
+    let max = smol::block_on(reduce(context(), ReduceOp::Max, &buffer)).unwrap();
+    let argmax = smol::block_on(arg_reduce(context(), ArgReduceOp::Max, &buffer)).unwrap();
+    assert_eq!(max, Some(u32::MAX));
+    assert_eq!(argmax, Some(1));
+}
+
+#[test]
+fn beyond_dispatch_limit() {
+    // more elements than 65535 workgroups of 256 invocations cover in one step
+    let len = 65_535 * 256 + 4097;
+    let data = vec![1u32; len];
+
+    let sum = smol::block_on(reduce(context(), ReduceOp::Sum, &upload(&data))).unwrap();
+
+    assert_eq!(sum, Some(len as u32));
+}
+
+#[test]
+fn buffer_without_storage_usage() {
+    let buffer = GpuBuffer::from_slice(context(), &[1u32, 2, 3, 4], BufferUsages::UNIFORM).unwrap();
+
+    let sum = smol::block_on(reduce(context(), ReduceOp::Sum, &buffer));
+    let argmax = smol::block_on(arg_reduce(context(), ArgReduceOp::Max, &buffer));
+
+    assert!(matches!(sum, Err(Error::InvalidInput(_))));
+    assert!(matches!(argmax, Err(Error::InvalidInput(_))));
+}
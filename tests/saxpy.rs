@@ -0,0 +1,70 @@
+mod common;
+
+use common::{context, Rng};
+use learning_wgpu::{axpy, saxpy, saxpy_strided, Error};
+
+#[test]
+fn separate_output() {
+    let x = vec![1.0, 2.0, 3.0, -4.0];
+    let y = vec![0.5, 0.25, 1.0, 2.0];
+
+    let result = smol::block_on(saxpy(context(), 2.0f32, &x, &y)).unwrap();
+
+    assert_eq!(result, [2.5, 4.25, 7.0, -6.0]);
+}
+
+#[test]
+fn strided() {
+    let mut rng = Rng::new(41);
+    let (x, y) = (rng.vec_i32(3 * 100, 50), rng.vec_i32(2 * 100, 50));
+
+    let result = smol::block_on(saxpy_strided(context(), 100, -3, &x, 3, &y, 2)).unwrap();
+
+    let expected: Vec<_> = (0..100).map(|i| -3 * x[i * 3] + y[i * 2]).collect();
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn in_place_keeps_skipped_elements() {
+    let mut rng = Rng::new(42);
+    let x = rng.vec_i32(50, 50);
+    let mut y = rng.vec_i32(3 * 50, 50);
+    let mut expected = y.clone();
+    for i in 0..50 {
+        expected[i * 3] += 7 * x[i];
+    }
+
+    smol::block_on(axpy(context(), 50, 7, &x, 1, &mut y, 3)).unwrap();
+
+    assert_eq!(y, expected);
+}
+
+#[test]
+fn more_elements_than_one_dispatch_dimension() {
+    let n = 65_535 * 64 + 1000;
+    let x: Vec<u32> = (0..n as u32).collect();
+    let mut y = vec![1; n];
+
+    smol::block_on(axpy(context(), n, 2, &x, 1, &mut y, 1)).unwrap();
+
+    assert!(y.iter().enumerate().all(|(i, &v)| v == 2 * i as u32 + 1));
+}
+
+#[test]
+fn invalid_lengths() {
+    let x = [1, 2, 3];
+
+    let mismatch = smol::block_on(saxpy(context(), 1, &x, &[1, 2]));
+    let short = smol::block_on(saxpy_strided(context(), 2, 1, &x, 3, &x, 1));
+    let zero_stride = smol::block_on(saxpy_strided(context(), 2, 1, &x, 0, &x, 1));
+
+    assert!(matches!(mismatch, Err(Error::LengthMismatch { .. })));
+    assert!(matches!(
+        short,
+        Err(Error::LengthMismatch {
+            expected: 4,
+            found: 3
+        })
+    ));
+    assert!(matches!(zero_stride, Err(Error::InvalidInput(_))));
+}
@@ -0,0 +1,139 @@
+mod common;
+
+use common::{context, Rng};
+use learning_wgpu::{buffer::GpuBuffer, scan, scan_buffers, Error, ScanMode};
+use wgpu::BufferUsages;
+
+fn cpu_scan(mode: ScanMode, data: &[i32]) -> Vec<i32> {
+    let mut acc = 0i32;
+    data.iter()
+        .map(|&value| {
+            let before = acc;
+            acc = acc.wrapping_add(value);
+            match mode {
+                ScanMode::Inclusive => acc,
+                ScanMode::Exclusive => before,
+            }
+        })
+        .collect()
+}
+
+fn check(rng: &mut Rng, len: usize) {
+    let data = rng.vec_i32(len, 1000);
+    for mode in [ScanMode::Inclusive, ScanMode::Exclusive] {
+        let expected = cpu_scan(mode, &data);
+
+        let result = smol::block_on(scan(context(), mode, &data)).unwrap();
+        assert_eq!(result, expected, "{mode:?} scan of {len}");
+
+        // unsigned wrapping sums match signed ones bit for bit
+        let unsigned: Vec<u32> = data.iter().map(|&v| v as u32).collect();
+        let result = smol::block_on(scan(context(), mode, &unsigned)).unwrap();
+        let expected: Vec<u32> = expected.iter().map(|&v| v as u32).collect();
+        assert_eq!(result, expected, "{mode:?} unsigned scan of {len}");
+    }
+}
+
+#[test]
+fn lengths_around_block_size() {
+    let mut rng = Rng::new(71);
+    for len in [0, 1, 2, 511, 512, 513, 1024, 512 * 512, 512 * 512 + 1] {
+        check(&mut rng, len);
+    }
+}
+
+#[test]
+fn random_lengths() {
+    let mut rng = Rng::new(72);
+    for _ in 0..20 {
+        let len = rng.next_u32() as usize % 200_000;
+        check(&mut rng, len);
+    }
+}
+
+#[test]
+fn floats() {
+    let mut rng = Rng::new(73);
+    // integer values keep every partial sum exact regardless of association
+    let data: Vec<f32> = rng
+        .vec_i32(100_000, 100)
+        .iter()
+        .map(|&v| v as f32)
+        .collect();
+    let ints: Vec<i32> = data.iter().map(|&v| v as i32).collect();
+
+    for mode in [ScanMode::Inclusive, ScanMode::Exclusive] {
+        let result = smol::block_on(scan(context(), mode, &data)).unwrap();
+        let expected: Vec<f32> = cpu_scan(mode, &ints).iter().map(|&v| v as f32).collect();
+        assert_eq!(result, expected, "{mode:?}");
+    }
+}
+
+#[test]
+fn tens_of_millions() {
+    // 160 MB, past 128 MiB storage binding of downlevel defaults,
+    // adapters that can't bind that much have to refuse instead of panicking
+    let len = 40_000_000;
+    let data = vec![1u32; len];
+
+    let result = smol::block_on(scan(context(), ScanMode::Exclusive, &data));
+
+    let max_binding = context().device.limits().max_storage_buffer_binding_size as usize;
+    if len * size_of::<u32>() > max_binding {
+        assert!(matches!(result, Err(Error::InvalidInput(_))));
+        return;
+    }
+    assert!(result
+        .unwrap()
+        .iter()
+        .enumerate()
+        .all(|(i, &v)| v == i as u32));
+}
+
+#[test]
+fn twenty_million() {
+    let len = 20_000_000;
+    let data = vec![1u32; len];
+
+    let result = smol::block_on(scan(context(), ScanMode::Exclusive, &data)).unwrap();
+
+    assert!(result.iter().enumerate().all(|(i, &v)| v == i as u32));
+}
+
+#[test]
+fn buffers_without_storage_usage() {
+    let x = GpuBuffer::from_slice(context(), &[1u32, 2, 3], BufferUsages::UNIFORM).unwrap();
+    let out = GpuBuffer::<u32>::zeroed(context(), 3, BufferUsages::STORAGE).unwrap();
+
+    let result = scan_buffers(context(), ScanMode::Inclusive, &x, &out);
+
+    assert!(matches!(result, Err(Error::InvalidInput(_))));
+}
+
+#[test]
+fn in_place() {
+    let mut rng = Rng::new(61);
+    let data = rng.vec_i32(5000, 100);
+    let x = GpuBuffer::from_slice(context(), &data, BufferUsages::STORAGE).unwrap();
+
+    scan_buffers(context(), ScanMode::Exclusive, &x, &x).unwrap();
+
+    let result = smol::block_on(x.download()).unwrap();
+    assert_eq!(result, cpu_scan(ScanMode::Exclusive, &data));
+}
+
+#[test]
+fn buffer_length_mismatch() {
+    let x = GpuBuffer::from_slice(context(), &[1, 2, 3], BufferUsages::STORAGE).unwrap();
+    let out = GpuBuffer::<i32>::zeroed(context(), 2, BufferUsages::STORAGE).unwrap();
+
+    let result = scan_buffers(context(), ScanMode::Inclusive, &x, &out);
+
+    assert!(matches!(
+        result,
+        Err(Error::LengthMismatch {
+            expected: 3,
+            found: 2
+        })
+    ));
+}
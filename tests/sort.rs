@@ -0,0 +1,121 @@
+mod common;
+
+use common::{context, Rng};
+use learning_wgpu::{
+    bitonic_sort, bitonic_sort_rows, radix_sort, radix_sort_f32, radix_sort_pairs,
+    radix_sort_pairs_f32, Error, Matrix,
+};
+
+#[test]
+fn random_keys() {
+    let mut rng = Rng::new(81);
+    for len in [0, 1, 2, 255, 256, 257, 10_000, 300_000] {
+        let keys: Vec<u32> = (0..len).map(|_| rng.next_u32()).collect();
+        let mut expected = keys.clone();
+        expected.sort();
+
+        let sorted = smol::block_on(radix_sort(context(), &keys)).unwrap();
+
+        assert_eq!(sorted, expected, "{len} keys");
+    }
+}
+
+#[test]
+fn pairs_are_stable() {
+    let mut rng = Rng::new(82);
+    // few distinct keys, so most pairs share key with others
+    let keys: Vec<u32> = (0..50_000).map(|_| rng.next_u32() % 37).collect();
+    let values: Vec<u32> = (0..keys.len() as u32).collect();
+    let mut expected: Vec<(u32, u32)> = keys.iter().copied().zip(values.iter().copied()).collect();
+    // slice::sort_by_key is stable
+    expected.sort_by_key(|&(key, _)| key);
+
+    let (sorted_keys, sorted_values) =
+        smol::block_on(radix_sort_pairs(context(), &keys, &values)).unwrap();
+
+    let sorted: Vec<(u32, u32)> = sorted_keys.into_iter().zip(sorted_values).collect();
+    assert_eq!(sorted, expected);
+}
+
+#[test]
+fn float_keys() {
+    let mut rng = Rng::new(83);
+    let mut keys: Vec<f32> = (0..20_000)
+        .map(|_| rng.small_i32(1_000_000) as f32 / 1000.0)
+        .collect();
+    keys.extend([
+        0.0,
+        -0.0,
+        f32::INFINITY,
+        f32::NEG_INFINITY,
+        f32::MIN,
+        f32::MAX,
+    ]);
+    let mut expected = keys.clone();
+    expected.sort_by(f32::total_cmp);
+
+    let sorted = smol::block_on(radix_sort_f32(context(), &keys)).unwrap();
+    let (paired, values) =
+        smol::block_on(radix_sort_pairs_f32(context(), &keys, &vec![0; keys.len()])).unwrap();
+
+    let bits = |data: &[f32]| data.iter().map(|v| v.to_bits()).collect::<Vec<_>>();
+    assert_eq!(bits(&sorted), bits(&expected));
+    assert_eq!(bits(&paired), bits(&expected));
+    assert_eq!(values.len(), keys.len());
+}
+
+#[test]
+fn pairs_length_mismatch() {
+    let result = smol::block_on(radix_sort_pairs(context(), &[3, 1], &[0]));
+
+    assert!(matches!(
+        result,
+        Err(Error::LengthMismatch {
+            expected: 2,
+            found: 1
+        })
+    ));
+}
+
+#[test]
+fn bitonic_lengths() {
+    let mut rng = Rng::new(84);
+    for len in [0, 1, 2, 3, 100, 255, 256, 257, 1000, 2048, 4095, 4096] {
+        let data = rng.vec_i32(len, 100_000);
+        let mut expected = data.clone();
+        expected.sort();
+
+        let sorted = smol::block_on(bitonic_sort(context(), &data)).unwrap();
+
+        assert_eq!(sorted, expected, "{len} elements");
+    }
+}
+
+#[test]
+fn bitonic_rows() {
+    let mut rng = Rng::new(85);
+    let (rows, cols) = (300, 77);
+    let data: Vec<f32> = rng
+        .vec_i32(rows * cols, 1000)
+        .iter()
+        .map(|&v| v as f32 / 7.0)
+        .collect();
+    let matrix = Matrix::new(data.clone(), rows, cols).unwrap();
+
+    let sorted = smol::block_on(bitonic_sort_rows(context(), &matrix)).unwrap();
+
+    let mut expected = data;
+    for row in expected.chunks_mut(cols) {
+        row.sort_by(f32::total_cmp);
+    }
+    assert_eq!(sorted.data(), expected);
+}
+
+#[test]
+fn bitonic_too_long() {
+    let data = vec![0u32; 4097];
+
+    let result = smol::block_on(bitonic_sort(context(), &data));
+
+    assert!(matches!(result, Err(Error::InvalidInput(_))));
+}
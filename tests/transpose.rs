@@ -0,0 +1,236 @@
+mod common;
+
+use common::{context, Rng};
+use learning_wgpu::{
+    buffer::GpuBuffer, permute, transpose, transpose_batched, transpose_in_place, transpose_tiled,
+    Error,
+};
+use wgpu::BufferUsages;
+
+// transposes `rows`×`cols` matrix on host
+fn transpose_cpu<T: Copy>(x: &[T], rows: usize, cols: usize) -> Vec<T> {
+    (0..cols)
+        .flat_map(|col| (0..rows).map(move |row| x[row * cols + col]))
+        .collect()
+}
+
+// permutes axes of row major tensor on host
+fn permute_cpu<T: Copy>(x: &[T], shape: &[usize], axes: &[usize]) -> Vec<T> {
+    let strides: Vec<usize> = (0..shape.len())
+        .map(|axis| shape[axis + 1..].iter().product())
+        .collect();
+    let out_shape: Vec<usize> = axes.iter().map(|&axis| shape[axis]).collect();
+    (0..x.len())
+        .map(|idx| {
+            let mut rest = idx;
+            let mut offset = 0;
+            for dim in (0..axes.len()).rev() {
+                offset += rest % out_shape[dim] * strides[axes[dim]];
+                rest /= out_shape[dim];
+            }
+            x[offset]
+        })
+        .collect()
+}
+
+#[test]
+fn rectangular_shapes() {
+    let mut rng = Rng::new(91);
+    for (rows, cols) in [
+        (1, 1),
+        (4, 4),
+        (1, 7),
+        (7, 1),
+        (3, 5),
+        (13, 17),
+        (64, 9),
+        (257, 131),
+    ] {
+        let x = rng.vec_i32(rows * cols, 1000);
+
+        let result = smol::block_on(transpose(context(), &x, rows, cols)).unwrap();
+
+        assert_eq!(result, transpose_cpu(&x, rows, cols), "{rows}×{cols}");
+    }
+}
+
+#[test]
+fn tiled_matches_naive() {
+    let mut rng = Rng::new(92);
+    for tile in [8, 16, 32] {
+        for (rows, cols) in [
+            (1, 1),
+            (1, 33),
+            (33, 1),
+            (16, 16),
+            (31, 65),
+            (100, 37),
+            (512, 300),
+        ] {
+            let x = rng.vec_i32(rows * cols, 1000);
+
+            let result = smol::block_on(transpose_tiled(context(), &x, rows, cols, tile)).unwrap();
+
+            assert_eq!(
+                result,
+                transpose_cpu(&x, rows, cols),
+                "{rows}×{cols} with tile {tile}"
+            );
+        }
+    }
+}
+
+#[test]
+fn tiled_rejects_bad_tile() {
+    for tile in [0, 12, 4096] {
+        let result = smol::block_on(transpose_tiled(context(), &[1.0f32; 6], 2, 3, tile));
+
+        assert!(matches!(result, Err(Error::InvalidInput(_))), "tile {tile}");
+    }
+}
+
+#[test]
+fn transposing_twice_restores_matrix() {
+    let (rows, cols) = (37, 101);
+    let x: Vec<f32> = (0..rows * cols).map(|idx| idx as f32 * 0.5).collect();
+
+    let once = smol::block_on(transpose(context(), &x, rows, cols)).unwrap();
+    let twice = smol::block_on(transpose(context(), &once, cols, rows)).unwrap();
+
+    assert_eq!(twice, x);
+}
+
+#[test]
+fn empty_matrix() {
+    let result = smol::block_on(transpose::<u32>(context(), &[], 0, 5)).unwrap();
+
+    assert!(result.is_empty());
+}
+
+#[test]
+fn length_mismatch() {
+    let result = smol::block_on(transpose(context(), &[1, 2, 3, 4, 5], 2, 3));
+
+    assert!(matches!(
+        result,
+        Err(Error::LengthMismatch {
+            expected: 6,
+            found: 5
+        })
+    ));
+}
+
+#[test]
+fn permute_matches_cpu() {
+    let mut rng = Rng::new(93);
+    let cases: [(&[usize], &[usize]); 8] = [
+        (&[], &[]),
+        (&[5], &[0]),
+        (&[3, 4, 5], &[2, 0, 1]),
+        // NCHW into NHWC
+        (&[2, 3, 8, 9], &[0, 2, 3, 1]),
+        (&[2, 3, 4, 5, 6], &[4, 3, 2, 1, 0]),
+        (&[2, 3, 1, 4, 2, 5], &[5, 1, 3, 0, 2, 4]),
+        (&[7, 1, 3], &[1, 2, 0]),
+        (&[64, 33, 17], &[1, 0, 2]),
+    ];
+    for (shape, axes) in cases {
+        let x = rng.vec_i32(shape.iter().product(), 1000);
+
+        let result = smol::block_on(permute(context(), &x, shape, axes)).unwrap();
+
+        assert_eq!(
+            result,
+            permute_cpu(&x, shape, axes),
+            "{shape:?} by {axes:?}"
+        );
+    }
+}
+
+#[test]
+fn permute_of_two_axes_is_transpose() {
+    let (rows, cols) = (29, 43);
+    let x: Vec<f32> = (0..rows * cols).map(|idx| idx as f32).collect();
+
+    let result = smol::block_on(permute(context(), &x, &[rows, cols], &[1, 0])).unwrap();
+
+    assert_eq!(result, transpose_cpu(&x, rows, cols));
+}
+
+#[test]
+fn permute_rejects_bad_axes() {
+    let x = [0u32; 24];
+    for axes in [&[0, 1][..], &[0, 1, 1], &[0, 1, 3]] {
+        let result = smol::block_on(permute(context(), &x, &[2, 3, 4], axes));
+
+        assert!(result.is_err(), "axes {axes:?}");
+    }
+    let result = smol::block_on(permute(context(), &[0u32; 64], &[2; 7], &[0; 7]));
+    assert!(matches!(result, Err(Error::InvalidInput(_))));
+}
+
+#[test]
+fn batched_transposes_every_matrix() {
+    let mut rng = Rng::new(94);
+    for (batch, rows, cols) in [(1, 5, 3), (3, 1, 1), (7, 13, 21), (40, 32, 9)] {
+        let x = rng.vec_i32(batch * rows * cols, 1000);
+        let expected: Vec<i32> = x
+            .chunks(rows * cols)
+            .flat_map(|matrix| transpose_cpu(matrix, rows, cols))
+            .collect();
+
+        let result = smol::block_on(transpose_batched(context(), &x, batch, rows, cols)).unwrap();
+
+        assert_eq!(result, expected, "{batch} of {rows}×{cols}");
+    }
+}
+
+#[test]
+fn in_place_square() {
+    let mut rng = Rng::new(95);
+    for (batch, n) in [
+        (1, 1),
+        (1, 15),
+        (1, 16),
+        (1, 17),
+        (2, 50),
+        (5, 64),
+        (3, 100),
+    ] {
+        let x = rng.vec_i32(batch * n * n, 1000);
+        let expected: Vec<i32> = x
+            .chunks(n * n)
+            .flat_map(|matrix| transpose_cpu(matrix, n, n))
+            .collect();
+        let buffer = GpuBuffer::from_slice(context(), &x, BufferUsages::STORAGE).unwrap();
+
+        transpose_in_place(context(), &buffer, batch, n).unwrap();
+
+        let result = smol::block_on(buffer.download()).unwrap();
+        assert_eq!(result, expected, "{batch} of {n}×{n}");
+    }
+}
+
+#[test]
+fn in_place_rejects_non_square_length() {
+    let buffer = GpuBuffer::from_slice(context(), &[0.0f32; 12], BufferUsages::STORAGE).unwrap();
+
+    let result = transpose_in_place(context(), &buffer, 1, 3);
+
+    assert!(matches!(
+        result,
+        Err(Error::LengthMismatch {
+            expected: 9,
+            found: 12
+        })
+    ));
+}
+
+#[test]
+fn in_place_rejects_buffer_without_storage_usage() {
+    let buffer = GpuBuffer::from_slice(context(), &[0.0f32; 4], BufferUsages::UNIFORM).unwrap();
+
+    let result = transpose_in_place(context(), &buffer, 1, 2);
+
+    assert!(matches!(result, Err(Error::InvalidInput(_))));
+}